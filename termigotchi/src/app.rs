@@ -1,37 +1,57 @@
-use crate::config::{load_settings, project_paths, save_settings_atomic, Settings};
+use crate::config::{
+    load_settings, parse_seed, project_paths, write_json_atomic, Paths, Settings, SettingsItem,
+    FPS_MAX, FPS_MIN,
+};
+use crate::food::{load_food_catalog, FoodCatalog};
+use crate::hooks::{run_hooks, take_events};
 use crate::input::{collect_input_nonblocking, map_event_to_action};
-use crate::model::{GameState, Rules, SaveFile, Scene, SAVE_VERSION};
+use crate::journal::JournalEvent;
+use crate::model::{
+    CatchupSummary, GameState, RNGState, Rules, SaveFile, Scene, HOUSEHOLD_MAX, SAVE_VERSION,
+};
 use crate::render::{
-    canvas_to_cells, draw_pet_ascii, draw_text, pet_bounce_offset_cells,
-    pet_bounce_offset_subpx, ui_overlay, Cell, Renderer, Terminal, Viewport,
+    canvas_to_cells, draw_meter_chart_labels, draw_meter_charts, draw_pet_ascii, draw_sky,
+    draw_sky_ascii, draw_text, pet_bounce_offset_cells, pet_bounce_offset_subpx, ui_overlay, Cell,
+    Renderer, Terminal, Viewport,
 };
+use crate::replay::Recorder;
+use crate::rules::{load_rules, RulesBook, DEFAULT_PRESET};
 use crate::sim::{catch_up, PlayerAction};
+use crate::skin::{available_skins, load_skin, Skin};
 use crate::storage::{load_or_init_save, save_atomic};
+use chrono::{DateTime, Utc};
 use std::cmp::{max, min};
+use std::path::Path;
 use std::time::{Duration, Instant};
 
 pub(crate) struct App {
     settings: Settings,
+    book: RulesBook,
     rules: Rules,
+    foods: FoodCatalog,
+    skin: Skin,
+    // why the last settings change did not go through (or the skin failed to load)
+    settings_note: Option<String>,
     state: GameState,
-    paths: crate::config::Paths,
+    paths: Paths,
     term: Terminal,
+    notice: Option<String>,
     should_quit: bool,
     autosave_at: Instant,
+    recorder: Option<Recorder>,
 }
 
 impl App {
-    fn init() -> anyhow::Result<Self> {
+    fn init(record: Option<&Path>) -> anyhow::Result<Self> {
         let paths = project_paths()?;
-        let mut settings = load_settings(&paths.settings_path);
-        let rules = Rules::default();
-
-        let (mut state, loaded_last_seen) = load_or_init_save(&paths.save_path, &settings)?;
-
-        // offline catch-up
-        if let Some(last_seen) = loaded_last_seen {
-            let now = chrono::Utc::now();
-            let summary = catch_up(&mut state, last_seen, now, &rules);
+        let mut settings = load_settings(&paths.settings_path)?;
+        let foods = load_food_catalog(&paths.foods_path)?;
+        let (skin, settings_note) = load_skin(&paths.skins_dir, &settings.skin_name);
+
+        let resumed = resume(&paths, &settings, Utc::now())?;
+        let mut state = resumed.state;
+        let rules = resumed.rules;
+        if let Some(summary) = resumed.summary {
             if summary.has_anything() {
                 state.scene = Scene::Recap(summary);
             }
@@ -42,52 +62,68 @@ impl App {
             settings.seed = 0xC0FFEE_u64;
         }
 
+        let recorder = record
+            .map(|path| Recorder::create(path, &rules, &foods, &state))
+            .transpose()?;
+
         let term = Terminal::begin()?;
 
         Ok(Self {
             settings,
+            book: resumed.book,
             rules,
+            foods,
+            skin,
+            settings_note,
             state,
             paths,
             term,
+            notice: resumed.notice,
             should_quit: false,
             autosave_at: Instant::now() + Duration::from_secs(10),
+            recorder,
         })
     }
 
     fn run(&mut self) -> anyhow::Result<()> {
-        let fps = self.settings.fps_cap.max(10).min(240);
-        let frame_dt = Duration::from_secs_f32(1.0 / fps as f32);
-        let sim_step = Duration::from_millis(self.rules.tick_step_ms);
-
         let mut last_frame = Instant::now();
         let mut sim_accum = Duration::ZERO;
 
         while !self.should_quit {
             let _resized = self.term.resize_if_needed()?;
+            // both can change mid-run (fps cap in Settings, rules on a new egg)
+            let fps = self.settings.fps_cap.clamp(FPS_MIN, FPS_MAX);
+            let frame_dt = Duration::from_secs_f32(1.0 / fps as f32);
+            let sim_step = Duration::from_millis(self.rules.tick_step_ms);
 
             // input
             let events = collect_input_nonblocking(frame_dt)?;
             for ev in events {
+                // a save notice swallows the first key
+                if self.notice.take().is_some() {
+                    continue;
+                }
                 if let Some(action) = map_event_to_action(&self.state.scene, ev) {
                     match action {
                         PlayerAction::Quit => {
                             self.should_quit = true;
                             break;
                         }
-                        PlayerAction::SettingsToggle => {
-                            if self.state.settings_cursor == 0 {
-                                self.settings.enable_braille = !self.settings.enable_braille;
-                            } else if self.state.settings_cursor == 1 {
-                                self.state.apply(PlayerAction::RenameOpen);
-                            }
+                        PlayerAction::SettingsToggle => self.activate_setting()?,
+                        PlayerAction::SettingsAdjust(delta) => self.adjust_setting(delta)?,
+                        PlayerAction::SeedEditCommit => self.commit_seed()?,
+                        PlayerAction::SeedEditClose => {
+                            self.settings_note = None;
+                            self.apply(action)?;
                         }
-                        _ => self.state.apply(action),
+                        PlayerAction::NewGame => self.hatch()?,
+                        PlayerAction::Adopt => self.adopt()?,
+                        _ => self.apply(action)?,
                     }
                 } else {
                     // For recap: any key continues
                     if matches!(self.state.scene, Scene::Recap(_)) {
-                        self.state.scene = Scene::Main;
+                        self.apply(PlayerAction::Back)?;
                     }
                 }
             }
@@ -99,12 +135,14 @@ impl App {
             sim_accum = sim_accum.saturating_add(real_dt);
 
             while sim_accum >= sim_step {
-                self.state.tick_fixed_step(&self.rules);
-                sim_accum = sim_accum.saturating_sub(sim_step);
-                if self.state.pet.flags.dead && !matches!(self.state.scene, Scene::Dead) {
-                    self.state.scene = Scene::Dead;
+                self.state.tick_live(&self.rules);
+                if let Some(rec) = &mut self.recorder {
+                    rec.step();
                 }
+                sim_accum = sim_accum.saturating_sub(sim_step);
             }
+            // also picks up whatever happened during catch-up, on the first frame
+            self.fire_hooks();
 
             // render
             self.render_frame()?;
@@ -121,10 +159,167 @@ impl App {
 
         self.save_now()?;
         self.term.end()?;
-        save_settings_atomic(&self.paths.settings_path, &self.settings)?;
+        write_json_atomic(&self.paths.settings_path, &self.settings)?;
+        if let Some(rec) = self.recorder.take() {
+            rec.finish()?;
+        }
+        Ok(())
+    }
+
+    // Everything that changes the game state goes through here so a recording can
+    // replay it.
+    fn apply(&mut self, action: PlayerAction) -> anyhow::Result<()> {
+        if let Some(rec) = &mut self.recorder {
+            rec.action(&action)?;
+        }
+        self.state.apply(action, &self.foods);
         Ok(())
     }
 
+    // Enter on a Settings row: edit, toggle or step it forward.
+    fn activate_setting(&mut self) -> anyhow::Result<()> {
+        match SettingsItem::ALL.get(self.state.settings_cursor) {
+            Some(SettingsItem::Seed) => {
+                self.settings_note = None;
+                self.apply(PlayerAction::SeedEditOpen(self.settings.seed.to_string()))
+            }
+            Some(SettingsItem::Name) => self.apply(PlayerAction::RenameOpen),
+            Some(SettingsItem::Reset) => {
+                // hook commands can only be typed into settings.json, so they survive
+                let hooks = std::mem::take(&mut self.settings.hooks);
+                self.settings = Settings {
+                    hooks,
+                    ..Settings::default()
+                };
+                self.reload_skin();
+                self.store_settings();
+                self.apply(PlayerAction::SetSchedule(self.settings.schedule()))
+            }
+            _ => self.adjust_setting(1),
+        }
+    }
+
+    // Left/right on a Settings row. Every change applies immediately and is saved.
+    fn adjust_setting(&mut self, delta: i32) -> anyhow::Result<()> {
+        let Some(item) = SettingsItem::ALL.get(self.state.settings_cursor) else {
+            return Ok(());
+        };
+        match item {
+            SettingsItem::FpsCap => {
+                let fps = self.settings.fps_cap as i32 + delta * 5;
+                self.settings.fps_cap = fps.clamp(FPS_MIN as i32, FPS_MAX as i32) as u32;
+            }
+            SettingsItem::Color => self.settings.enable_color = !self.settings.enable_color,
+            SettingsItem::Braille => self.settings.enable_braille = !self.settings.enable_braille,
+            SettingsItem::Skin => {
+                let names = available_skins(&self.paths.skins_dir);
+                self.settings.skin_name = cycle(&names, &self.settings.skin_name, delta);
+                self.reload_skin();
+            }
+            SettingsItem::RulesPreset => {
+                let names = self.book.names();
+                self.settings.rules_preset = cycle(&names, &self.settings.rules_preset, delta);
+            }
+            SettingsItem::Inheritance => self.settings.inheritance = !self.settings.inheritance,
+            SettingsItem::Schedule => self.settings.schedule = !self.settings.schedule,
+            SettingsItem::Hooks => self.settings.hooks.enabled = !self.settings.hooks.enabled,
+            SettingsItem::Bedtime => {
+                self.settings.bedtime_hour =
+                    step_hour(self.settings.bedtime_hour, delta, self.settings.wake_hour);
+            }
+            SettingsItem::WakeTime => {
+                self.settings.wake_hour =
+                    step_hour(self.settings.wake_hour, delta, self.settings.bedtime_hour);
+            }
+            SettingsItem::Seed | SettingsItem::Name | SettingsItem::Reset => return Ok(()),
+        }
+        self.store_settings();
+        if matches!(
+            item,
+            SettingsItem::Schedule | SettingsItem::Bedtime | SettingsItem::WakeTime
+        ) {
+            self.apply(PlayerAction::SetSchedule(self.settings.schedule()))?;
+        }
+        Ok(())
+    }
+
+    fn commit_seed(&mut self) -> anyhow::Result<()> {
+        match parse_seed(&self.state.seed_edit) {
+            Ok(seed) => {
+                self.settings.seed = seed;
+                self.store_settings();
+                self.apply(PlayerAction::SeedEditClose)
+            }
+            Err(e) => {
+                self.settings_note = Some(format!("{e:#}"));
+                Ok(())
+            }
+        }
+    }
+
+    fn reload_skin(&mut self) {
+        let (skin, note) = load_skin(&self.paths.skins_dir, &self.settings.skin_name);
+        self.skin = skin;
+        self.settings_note = note;
+    }
+
+    // Like a failed settings write, a hook that will not start is only reported.
+    fn fire_hooks(&mut self) {
+        let events = take_events(&mut self.state, &self.rules);
+        let hooks = &self.settings.hooks;
+        if let Err(e) = run_hooks(hooks, &mut self.state.hook_log, events, Utc::now()) {
+            self.settings_note = Some(format!("{e:#}"));
+        }
+    }
+
+    // A failed write is shown in Settings rather than ending the game.
+    fn store_settings(&mut self) {
+        if let Err(e) = write_json_atomic(&self.paths.settings_path, &self.settings) {
+            self.settings_note = Some(format!("Could not save settings: {e:#}"));
+        }
+    }
+
+    // A fresh household takes the seed and rules preset currently chosen in Settings. An
+    // egg that joins living housemates lives under their rules, on a seed derived from
+    // the Settings one so no two pets share a stream.
+    fn hatch(&mut self) -> anyhow::Result<()> {
+        if !self.state.all_dead() {
+            return self.apply(PlayerAction::Hatch {
+                seed: RNGState::derive_seed(self.settings.seed, self.state.hatched),
+                rules_preset: self.state.rules_preset.clone(),
+                inherit: self.settings.inheritance,
+            });
+        }
+        let mut preset = self.settings.rules_preset.clone();
+        if self.book.get(&preset).is_none() {
+            preset = DEFAULT_PRESET.to_string();
+        }
+        let seed = self.settings.seed;
+        self.apply(PlayerAction::Hatch {
+            seed,
+            rules_preset: preset.clone(),
+            inherit: self.settings.inheritance,
+        })?;
+
+        if let Some(rules) = self.book.get(&preset) {
+            self.rules = rules.clone();
+            if let Some(rec) = &mut self.recorder {
+                rec.rules(&self.rules)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn adopt(&mut self) -> anyhow::Result<()> {
+        // the household strip says when it is full
+        if self.state.pets.len() >= HOUSEHOLD_MAX {
+            return Ok(());
+        }
+        self.apply(PlayerAction::AdoptEgg {
+            seed: RNGState::derive_seed(self.settings.seed, self.state.hatched),
+        })
+    }
+
     fn render_frame(&mut self) -> anyhow::Result<()> {
         let bg = crossterm::style::Color::Black;
         self.term.cur.clear(bg);
@@ -153,8 +348,22 @@ impl App {
                 w: pet_w_cells * 2,
                 h: pet_h_cells * 4,
             };
-            let bounce = pet_bounce_offset_subpx(&self.state);
-            Renderer::draw_pet(&mut self.term.canvas, &self.state, vp, bounce);
+            let history = matches!(self.state.scene, Scene::History);
+            if history {
+                draw_meter_charts(
+                    &mut self.term.canvas,
+                    &self.state.pet().journal,
+                    pet_x_cells,
+                    pet_w_cells,
+                );
+            } else if !matches!(self.state.scene, Scene::Graveyard) {
+                draw_sky(&mut self.term.canvas, &self.state, vp);
+                let bounce = pet_bounce_offset_subpx(&self.state);
+                Renderer::draw_pet(&mut self.term.canvas, &self.skin, &self.state, vp, bounce);
+                if self.state.lights_off {
+                    self.term.canvas.dim(0.35);
+                }
+            }
 
             canvas_to_cells(
                 &self.term.canvas,
@@ -162,7 +371,26 @@ impl App {
                 self.settings.enable_color,
                 bg,
             );
-        } else {
+            if history {
+                draw_meter_chart_labels(
+                    &mut self.term.cur,
+                    &self.state.pet().journal,
+                    pet_x_cells as u16,
+                    self.rules.tick_step_ms,
+                );
+            }
+        } else if matches!(self.state.scene, Scene::History) {
+            let cols = self.term.cols as i32;
+            let panel_w_cells = min(max(26, cols / 3), cols - 10);
+            draw_text(
+                &mut self.term.cur,
+                panel_w_cells as u16 + 1,
+                2,
+                "Charts need Braille rendering (Tab → Render).",
+                crossterm::style::Color::DarkGrey,
+                bg,
+            );
+        } else if !matches!(self.state.scene, Scene::Graveyard) {
             // ASCII fallback: layered sprite approximation
             let cols = self.term.cols as i32;
             let rows = self.term.rows as i32;
@@ -172,28 +400,38 @@ impl App {
             let pet_h_cells = rows;
             let vx = pet_x_cells + pet_w_cells / 2;
             let vy = pet_h_cells / 2;
+            draw_sky_ascii(&mut self.term.cur, &self.state, pet_x_cells, pet_w_cells);
             let bounce = pet_bounce_offset_cells(&self.state);
             draw_pet_ascii(&mut self.term.cur, &self.state, vx + bounce.0, vy + bounce.1);
         }
 
         // UI overlay on top
-        ui_overlay(&mut self.term.cur, &self.state, &self.settings);
+        ui_overlay(
+            &mut self.term.cur,
+            &self.state,
+            &self.settings,
+            &self.foods,
+            self.settings_note.as_deref(),
+            self.rules.tick_step_ms,
+        );
 
         // Recap overlay
         if let Scene::Recap(ref s) = self.state.scene {
-            self.draw_center_box(
-                "While you were away…",
-                &format!(
-                    "Simulated {} ticks\nMin hunger: {:.1}\nMin happiness: {:.1}\nMin health: {:.1}\nAttention calls: {}\nSick: {}\nDied: {}\n\nPress any key",
-                    s.ticks_simulated,
-                    s.hunger_min,
-                    s.happiness_min,
-                    s.health_min,
-                    s.attention_calls,
-                    s.became_sick,
-                    s.died
-                ),
-            )?;
+            let mut body = format!("Simulated {} ticks\n", s.ticks_simulated);
+            for pet in &s.pets {
+                body.push_str(&format!(
+                    "\n{}: min hunger {:.0}, happiness {:.0}, health {:.0}\n  Attention calls: {}  Sick: {}  Died: {}\n",
+                    pet.name,
+                    pet.hunger_min,
+                    pet.happiness_min,
+                    pet.health_min,
+                    pet.attention_calls,
+                    pet.became_sick,
+                    pet.died
+                ));
+            }
+            body.push_str("\nPress any key");
+            self.draw_center_box("While you were away…", &body)?;
         }
 
         // Help overlay
@@ -202,14 +440,20 @@ impl App {
                 "How to play",
                 "Goal: keep your pet healthy and happy as it ages.\n\
     Watch the meters on the left; low stats hurt mood.\n\n\
-    F Feed: +hunger/+happiness, may create poop.\n\
-    P Play: +happiness, -energy, increases hunger.\n\
+    F Feed: pick a snack, meal, treat or special food.\n\
+    Favorites cheer your pet up; disliked food sulks.\n\
+    P Play: a mini-game; the better it goes, the happier.\n\
     C Clean: removes dirt/poop.\n\
     M Medicine: cures sickness.\n\
     S Sleep: toggle rest to regain energy.\n\
-    D Discipline: clears attention calls, boosts discipline.\n\n\
+    D Discipline: clears attention calls, boosts discipline.\n\
+    L Lights: with a bedtime schedule, dark means sleep.\n\
+    J Journal: your pet's life so far, with meter charts.\n\
+    G Graveyard: every pet you have lost.\n\
+    A Adopt another egg; ←/→ switch between pets.\n\n\
     Neglect (dirty/sick/low stats) drains health over time.\n\
-    Tab opens Settings (toggle render, rename pet).\n\n\
+    How you raise it decides what it grows into.\n\
+    Tab opens Settings (display, skin, name, seed, rules, bedtime).\n\n\
     Esc or H to close help.",
             )?;
         }
@@ -229,11 +473,65 @@ impl App {
             )?;
         }
 
+        // Seed overlay
+        if let Scene::SeedEdit = self.state.scene {
+            let error = self.settings_note.clone().unwrap_or_default();
+            self.draw_center_box(
+                "Seed for the next egg",
+                &format!(
+                    "Decimal, or hex starting with 0x.\n\nSeed: {}_\n\n{}\n\nEnter save | Esc cancel | Backspace delete",
+                    self.state.seed_edit, error
+                ),
+            )?;
+        }
+
         // Dead overlay
         if let Scene::Dead = self.state.scene {
+            let body = if self.state.all_dead() {
+                "Press N for new game, G for the graveyard, or Q to quit."
+            } else {
+                "Press N to hatch an egg in its place,\n←/→ to visit the others, or Q to quit."
+            };
+            self.draw_center_box("Your Termigotchi has passed on.", body)?;
+        }
+
+        // Farewell overlay (natural end of life)
+        if let Scene::Farewell = self.state.scene {
+            let pet = &self.state.pet();
+            let h = &pet.history;
+            let age_mins = pet.age_ticks * self.rules.tick_step_ms / 60_000;
             self.draw_center_box(
-                "Your Termigotchi has passed on.",
-                "Press N for new game, or Q to quit.",
+                &format!("Farewell, {}", pet.name),
+                &format!(
+                    "{} the {} lived a long, full life
+and drifted off to sleep for good.
+
+Age: {}h {:02}m
+Meals: {}  Games: {}  Baths: {}
+Lessons: {}  Illnesses: {}  Ignored calls: {}
+
+Thank you for looking after them.
+
+Press N to hatch a new egg, ←/→ for other pets, or Q to quit.",
+                    pet.name,
+                    pet.species_id,
+                    age_mins / 60,
+                    age_mins % 60,
+                    h.feed_events,
+                    h.play_events,
+                    h.clean_events,
+                    h.discipline_events,
+                    h.sickness_events,
+                    h.neglected_events
+                ),
+            )?;
+        }
+
+        // Save problem notice, above everything else
+        if let Some(notice) = self.notice.clone() {
+            self.draw_center_box(
+                "Save file problem",
+                &format!("{notice}\n\nPress any key to continue"),
             )?;
         }
 
@@ -245,8 +543,9 @@ impl App {
         let w = self.term.cols;
         let h = self.term.rows;
 
-        let bw = min(60, w.saturating_sub(4));
-        let bh = min(18, h.saturating_sub(4));
+        let longest = body.lines().map(|l| l.chars().count()).max().unwrap_or(0) as u16;
+        let bw = min(max(60, longest + 4), w.saturating_sub(4));
+        let bh = min(max(18, body.lines().count() as u16 + 5), h.saturating_sub(4));
 
         let x0 = (w - bw) / 2;
         let y0 = (h - bh) / 2;
@@ -348,8 +647,8 @@ impl App {
         );
 
         // body
-        let mut yy = y0 + 3;
-        for line in body.lines() {
+        for (i, line) in body.lines().enumerate() {
+            let yy = y0 + 3 + i as u16;
             if yy >= y0 + bh - 1 {
                 break;
             }
@@ -361,26 +660,117 @@ impl App {
                 crossterm::style::Color::White,
                 crossterm::style::Color::Black,
             );
-            yy += 1;
         }
 
         Ok(())
     }
 
     fn save_now(&self) -> anyhow::Result<()> {
-        let now = chrono::Utc::now();
-        let save = SaveFile {
-            version: SAVE_VERSION,
-            last_seen_utc: now,
-            state: self.state.clone(),
-        };
-        save_atomic(&self.paths.save_path, &save)?;
-        Ok(())
+        write_save(&self.paths, &self.state, &self.rules, Utc::now())
+    }
+}
+
+pub(crate) struct Resumed {
+    pub(crate) state: GameState,
+    pub(crate) book: RulesBook,
+    pub(crate) rules: Rules,
+    pub(crate) summary: Option<CatchupSummary>,
+    pub(crate) notice: Option<String>,
+}
+
+// Load the save and simulate the time away; shared by the TUI and headless commands.
+// The absence is replayed under the rules stored in the save, then play continues under
+// the pet's preset as currently defined in rules.json.
+pub(crate) fn resume(
+    paths: &Paths,
+    settings: &Settings,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Resumed> {
+    let book = load_rules(&paths.rules_path)?;
+    let loaded = load_or_init_save(&paths.save_path, settings)?;
+    let mut state = loaded.state;
+
+    if loaded.last_seen.is_none() {
+        state.rules_preset = settings.rules_preset.clone();
+        if book.get(&state.rules_preset).is_none() {
+            state.rules_preset = DEFAULT_PRESET.to_string();
+        }
+    }
+    let rules = book
+        .get(&state.rules_preset)
+        .cloned()
+        .or_else(|| loaded.rules.clone())
+        .unwrap_or_default();
+
+    let summary = loaded.last_seen.map(|last_seen| {
+        let away_rules = loaded.rules.as_ref().unwrap_or(&rules);
+        let summary = catch_up(&mut state, last_seen, now, away_rules);
+        let secs = summary.ticks_simulated * away_rules.tick_step_ms / 1000;
+        for (pet, recap) in state.pets.iter_mut().zip(&summary.pets) {
+            if summary.ticks_simulated > 0 && recap.has_anything() {
+                pet.log(
+                    now,
+                    JournalEvent::Away {
+                        secs,
+                        attention_calls: recap.attention_calls,
+                        became_sick: recap.became_sick,
+                    },
+                );
+            }
+        }
+        summary
+    });
+    // time away ran on the schedule in the save; from here on it is the one in Settings
+    state.clock = now;
+    state.set_schedule(settings.schedule());
+
+    Ok(Resumed {
+        state,
+        book,
+        rules,
+        summary,
+        notice: loaded.notice,
+    })
+}
+
+pub(crate) fn write_save(
+    paths: &Paths,
+    state: &GameState,
+    rules: &Rules,
+    now: DateTime<Utc>,
+) -> anyhow::Result<()> {
+    let save = SaveFile {
+        version: SAVE_VERSION,
+        last_seen_utc: now,
+        rules: rules.clone(),
+        state: state.clone(),
+    };
+    save_atomic(&paths.save_path, &save)
+}
+
+// Move an hour of the day by `delta`, skipping over `other` so bedtime and wake-up never
+// coincide.
+fn step_hour(hour: u32, delta: i32, other: u32) -> u32 {
+    let next = |h: u32| (h as i32 + delta).rem_euclid(24) as u32;
+    let stepped = next(hour);
+    if stepped == other {
+        next(stepped)
+    } else {
+        stepped
+    }
+}
+
+fn cycle(names: &[String], current: &str, delta: i32) -> String {
+    if names.is_empty() {
+        return current.to_string();
     }
+    let len = names.len() as i32;
+    let at = names.iter().position(|n| n == current).unwrap_or(0) as i32;
+    names[(at + delta).rem_euclid(len) as usize].clone()
 }
 
-pub(crate) fn run() -> anyhow::Result<()> {
-    let mut app = App::init()?;
+pub(crate) fn run(record: Option<&Path>) -> anyhow::Result<()> {
+    let mut app = App::init(record)?;
     app.run()?;
     Ok(())
 }
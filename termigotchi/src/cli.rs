@@ -0,0 +1,313 @@
+use crate::app::{resume, write_save};
+use crate::config::{load_settings, project_paths};
+use crate::food::load_food_catalog;
+use crate::hooks::{run_hooks, take_events};
+use crate::model::{DeathCause, Flags, GameState, LifeStage, Meters, Mood, Pet, Rules};
+use crate::replay::replay;
+use crate::sim::PlayerAction;
+use anyhow::{bail, Result};
+use chrono::Utc;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser, Debug)]
+#[command(name = "termigotchi")]
+#[command(about = "A Tamagotchi-like digital pet for the terminal", long_about = None)]
+pub(crate) struct Cli {
+    /// Run a single care action without opening the TUI
+    #[command(subcommand)]
+    pub(crate) command: Option<Command>,
+
+    /// Record every action of this session to FILE for `replay`
+    #[arg(long, value_name = "FILE")]
+    pub(crate) record: Option<PathBuf>,
+
+    /// Pet to act on, by name or by position (1 = first); defaults to the one selected in the game
+    #[arg(long, value_name = "NAME|N", global = true)]
+    pub(crate) pet: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum Command {
+    /// Show how the pet is doing
+    Status {
+        /// Print machine-readable JSON instead of text
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+    /// Feed the pet (see foods.json for ids)
+    Feed {
+        /// Food id; defaults to the first food in the catalog
+        food: Option<String>,
+    },
+    /// Clean up dirt and poop
+    Clean,
+    /// Give medicine (only does anything when sick)
+    Medicine,
+    /// Put the pet to bed
+    Sleep,
+    /// Wake the pet up
+    Wake,
+    /// Rebuild the final state of a session recorded with --record
+    Replay {
+        /// Recording to replay
+        file: PathBuf,
+        /// Print machine-readable JSON instead of text
+        #[arg(long, default_value_t = false)]
+        json: bool,
+    },
+}
+
+#[derive(Serialize)]
+struct StatusReport<'a> {
+    name: &'a str,
+    species: &'a str,
+    stage: LifeStage,
+    mood: Mood,
+    age_secs: u64,
+    rules_preset: &'a str,
+    meters: Meters,
+    flags: Flags,
+    cause_of_death: Option<DeathCause>,
+}
+
+// Every pet, and which one is selected (0-based): the one named with --pet, or else the
+// game's own.
+#[derive(Serialize)]
+struct HouseholdReport<'a> {
+    selected: usize,
+    pets: Vec<StatusReport<'a>>,
+}
+
+// A 1-based position, or a name (case-insensitive).
+fn find_pet(st: &GameState, wanted: &str) -> Result<usize> {
+    if let Ok(n) = wanted.parse::<usize>() {
+        if (1..=st.pets.len()).contains(&n) {
+            return Ok(n - 1);
+        }
+        bail!("there is no pet {n}; the household has {}", st.pets.len());
+    }
+    if let Some(i) = st
+        .pets
+        .iter()
+        .position(|p| p.name.eq_ignore_ascii_case(wanted))
+    {
+        return Ok(i);
+    }
+    let names: Vec<&str> = st.pets.iter().map(|p| p.name.as_str()).collect();
+    bail!(
+        "no pet named '{wanted}' (the household has: {})",
+        names.join(", ")
+    )
+}
+
+pub(crate) fn run_command(cmd: Command, pet: Option<&str>) -> Result<()> {
+    // a replay never touches the save
+    if let Command::Replay { file, json } = &cmd {
+        return run_replay(file, *json, pet);
+    }
+
+    let paths = project_paths()?;
+    let settings = load_settings(&paths.settings_path)?;
+    let foods = load_food_catalog(&paths.foods_path)?;
+
+    let now = Utc::now();
+    let resumed = resume(&paths, &settings, now)?;
+    if let Some(notice) = &resumed.notice {
+        eprintln!("{notice}");
+    }
+    let mut state = resumed.state;
+    let rules = resumed.rules;
+
+    // a status check only reads: polling it must not run hooks or rotate the backups
+    if let Command::Status { json } = cmd {
+        if let Some(wanted) = pet {
+            state.selected = find_pet(&state, wanted)?;
+        }
+        if json {
+            print_status_json(&state, &rules)?;
+        } else if pet.is_some() {
+            print_status(state.pet(), &state, &rules);
+        } else {
+            print_household(&state, &rules);
+        }
+        return Ok(());
+    }
+
+    // whatever happened while nobody was looking
+    let events = take_events(&mut state, &rules);
+    if let Err(e) = run_hooks(&settings.hooks, &mut state.hook_log, events, now) {
+        eprintln!("{e:#}");
+    }
+    // the game's own selection is left as it was
+    let selected = state.selected;
+    if let Some(wanted) = pet {
+        state.selected = find_pet(&state, wanted)?;
+    }
+
+    let name = state.pet().name.clone();
+    if state.pet().flags.dead {
+        state.selected = selected;
+        write_save(&paths, &state, &rules, now)?;
+        bail!("{name} has passed on; open the game to start a new egg");
+    }
+
+    match cmd {
+        Command::Feed { food } => {
+            let Some(id) = food.or_else(|| foods.foods.first().map(|f| f.id.clone())) else {
+                bail!("the food catalog is empty");
+            };
+            let Some(item) = foods.get(&id) else {
+                let ids: Vec<&str> = foods.foods.iter().map(|f| f.id.as_str()).collect();
+                bail!("unknown food '{id}' (try one of: {})", ids.join(", "));
+            };
+            println!("{name} ate the {}.", item.name.to_lowercase());
+            state.apply(PlayerAction::Feed(id), &foods);
+        }
+        Command::Clean => {
+            state.apply(PlayerAction::Clean, &foods);
+            println!("{name} is squeaky clean.");
+        }
+        Command::Medicine => {
+            if state.pet().flags.sick {
+                state.apply(PlayerAction::Medicine, &foods);
+                println!("{name} took the medicine and feels better.");
+            } else {
+                println!("{name} isn't sick.");
+            }
+        }
+        Command::Sleep => {
+            if state.pet().flags.sleeping {
+                println!("{name} is already asleep.");
+            } else {
+                state.apply(PlayerAction::SleepToggle, &foods);
+                println!("{name} curls up and falls asleep.");
+            }
+        }
+        Command::Wake => {
+            if state.pet().flags.sleeping {
+                state.apply(PlayerAction::SleepToggle, &foods);
+                println!("{name} wakes up.");
+            } else {
+                println!("{name} is already awake.");
+            }
+        }
+        Command::Status { .. } | Command::Replay { .. } => unreachable!("handled above"),
+    }
+
+    state.selected = selected;
+    write_save(&paths, &state, &rules, now)
+}
+
+fn run_replay(file: &Path, json: bool, pet: Option<&str>) -> Result<()> {
+    let mut replayed = replay(file)?;
+    if !replayed.complete {
+        eprintln!("Recording has no end marker; replayed up to its last action.");
+    }
+    if let Some(wanted) = pet {
+        replayed.state.selected = find_pet(&replayed.state, wanted)?;
+    }
+    if json {
+        print_status_json(&replayed.state, &replayed.rules)
+    } else {
+        println!("Replayed {} steps.", replayed.steps);
+        if pet.is_some() {
+            print_status(replayed.state.pet(), &replayed.state, &replayed.rules);
+        } else {
+            print_household(&replayed.state, &replayed.rules);
+        }
+        Ok(())
+    }
+}
+
+fn print_status_json(st: &GameState, rules: &Rules) -> Result<()> {
+    let report = HouseholdReport {
+        selected: st.selected,
+        pets: st
+            .pets
+            .iter()
+            .map(|pet| status_report(pet, st, rules))
+            .collect(),
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+fn status_report<'a>(pet: &'a Pet, st: &'a GameState, rules: &Rules) -> StatusReport<'a> {
+    StatusReport {
+        name: &pet.name,
+        species: &pet.species_id,
+        stage: pet.stage,
+        mood: pet.mood,
+        age_secs: pet.age_ticks * rules.tick_step_ms / 1000,
+        rules_preset: &st.rules_preset,
+        meters: pet.meters,
+        flags: pet.flags,
+        cause_of_death: pet.cause_of_death,
+    }
+}
+
+fn print_household(st: &GameState, rules: &Rules) {
+    for (i, pet) in st.pets.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_status(pet, st, rules);
+    }
+}
+
+fn print_status(pet: &Pet, st: &GameState, rules: &Rules) {
+    let age_mins = pet.age_ticks * rules.tick_step_ms / 60_000;
+    println!(
+        "{} the {} ({:?}), age {}h {:02}m, mood {:?}, {} rules",
+        pet.name,
+        pet.species_id,
+        pet.stage,
+        age_mins / 60,
+        age_mins % 60,
+        pet.mood,
+        st.rules_preset
+    );
+    if pet.flags.dead {
+        println!("{} has passed on.", pet.name);
+        return;
+    }
+
+    let m = pet.meters;
+    for (label, val) in [
+        ("Hunger", m.hunger),
+        ("Happy", m.happiness),
+        ("Health", m.health),
+        ("Energy", m.energy),
+        ("Hygiene", m.hygiene),
+    ] {
+        let fill = ((val / 10.0).round() as usize).min(10);
+        println!(
+            "{label:<8}[{}{}] {val:>5.1}",
+            "#".repeat(fill),
+            " ".repeat(10 - fill)
+        );
+    }
+
+    let f = pet.flags;
+    let mut notes = Vec::new();
+    if f.sleeping {
+        notes.push("sleeping");
+    }
+    if f.sick {
+        notes.push("sick");
+    }
+    if f.dirty {
+        notes.push("dirty");
+    }
+    if f.has_poop {
+        notes.push("poop");
+    }
+    if f.attention_call {
+        notes.push("wants attention");
+    }
+    if !notes.is_empty() {
+        println!("Status: {}", notes.join(", "));
+    }
+}
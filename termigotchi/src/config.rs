@@ -1,8 +1,12 @@
-use anyhow::{Context, Result};
+use crate::hooks::Hooks;
+use crate::model::Schedule;
+use crate::rules::DEFAULT_PRESET;
+use anyhow::{bail, Context, Result};
+use chrono::Local;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use std::{
-    fs,
+    fs, io,
     path::{Path, PathBuf},
 };
 
@@ -13,6 +17,49 @@ pub(crate) struct Settings {
     pub(crate) enable_color: bool,
     pub(crate) enable_braille: bool,
     pub(crate) seed: u64,
+    // preset for new pets; an existing pet keeps the one it was raised under
+    #[serde(default = "default_rules_preset")]
+    pub(crate) rules_preset: String,
+    // an egg hatched in a dead pet's place takes after it
+    #[serde(default = "default_inheritance")]
+    pub(crate) inheritance: bool,
+    // put pets to bed by the local clock
+    #[serde(default)]
+    pub(crate) schedule: bool,
+    #[serde(default = "default_bedtime_hour")]
+    pub(crate) bedtime_hour: u32,
+    #[serde(default = "default_wake_hour")]
+    pub(crate) wake_hour: u32,
+    // commands are set in settings.json; the Settings scene only switches them on and off
+    #[serde(default)]
+    pub(crate) hooks: Hooks,
+}
+
+fn default_rules_preset() -> String {
+    DEFAULT_PRESET.to_string()
+}
+
+fn default_inheritance() -> bool {
+    true
+}
+
+fn default_bedtime_hour() -> u32 {
+    21
+}
+
+fn default_wake_hour() -> u32 {
+    7
+}
+
+impl Settings {
+    // The schedule to hand the sim, pinned to today's local UTC offset.
+    pub(crate) fn schedule(&self) -> Option<Schedule> {
+        self.schedule.then(|| Schedule {
+            bedtime_hour: self.bedtime_hour % 24,
+            wake_hour: self.wake_hour % 24,
+            utc_offset_secs: Local::now().offset().local_minus_utc(),
+        })
+    }
 }
 
 impl Default for Settings {
@@ -23,13 +70,75 @@ impl Default for Settings {
             enable_color: true,
             enable_braille: true,
             seed: 0xC0FFEE_u64,
+            rules_preset: default_rules_preset(),
+            inheritance: default_inheritance(),
+            schedule: false,
+            bedtime_hour: default_bedtime_hour(),
+            wake_hour: default_wake_hour(),
+            hooks: Hooks::default(),
         }
     }
 }
 
+pub(crate) const FPS_MIN: u32 = 10;
+pub(crate) const FPS_MAX: u32 = 240;
+
+// Rows of the Settings scene, top to bottom.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SettingsItem {
+    FpsCap,
+    Color,
+    Braille,
+    Skin,
+    Seed,
+    Name,
+    RulesPreset,
+    Inheritance,
+    Schedule,
+    Bedtime,
+    WakeTime,
+    Hooks,
+    Reset,
+}
+
+impl SettingsItem {
+    pub(crate) const ALL: [SettingsItem; 13] = [
+        SettingsItem::FpsCap,
+        SettingsItem::Color,
+        SettingsItem::Braille,
+        SettingsItem::Skin,
+        SettingsItem::Seed,
+        SettingsItem::Name,
+        SettingsItem::RulesPreset,
+        SettingsItem::Inheritance,
+        SettingsItem::Schedule,
+        SettingsItem::Bedtime,
+        SettingsItem::WakeTime,
+        SettingsItem::Hooks,
+        SettingsItem::Reset,
+    ];
+}
+
+// Seeds are typed as decimal or 0x-prefixed hex. 0 is reserved for "pick the default".
+pub(crate) fn parse_seed(input: &str) -> Result<u64> {
+    let s = input.trim();
+    let seed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse::<u64>(),
+    }
+    .with_context(|| format!("'{s}' is not a number"))?;
+    if seed == 0 {
+        bail!("seed must not be 0");
+    }
+    Ok(seed)
+}
+
 pub(crate) struct Paths {
     pub(crate) save_path: PathBuf,
     pub(crate) settings_path: PathBuf,
+    pub(crate) foods_path: PathBuf,
+    pub(crate) skins_dir: PathBuf,
+    pub(crate) rules_path: PathBuf,
 }
 
 pub(crate) fn project_paths() -> Result<Paths> {
@@ -40,24 +149,27 @@ pub(crate) fn project_paths() -> Result<Paths> {
     Ok(Paths {
         save_path: dir.join("save.json"),
         settings_path: dir.join("settings.json"),
+        foods_path: dir.join("foods.json"),
+        skins_dir: dir.join("skins"),
+        rules_path: dir.join("rules.json"),
     })
 }
 
-pub(crate) fn load_settings(path: &Path) -> Settings {
-    if let Ok(s) = fs::read_to_string(path) {
-        if let Ok(v) = serde_json::from_str::<Settings>(&s) {
-            return v;
-        }
+// Defaults when there is no settings.json yet. A file that cannot be read is an error
+// rather than a reset: the game saves settings as it goes and would drop hand-set hooks.
+pub(crate) fn load_settings(path: &Path) -> Result<Settings> {
+    match fs::read_to_string(path) {
+        Ok(s) => serde_json::from_str(&s).with_context(|| format!("parsing {}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+        Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
     }
-    Settings::default()
 }
 
-pub(crate) fn save_settings_atomic(path: &Path, s: &Settings) -> Result<()> {
+// Write `value` as pretty JSON to a temporary file beside `path`, then swap it in.
+pub(crate) fn write_json_atomic<T: Serialize>(path: &Path, value: &T) -> Result<()> {
     let tmp = path.with_extension("json.tmp");
-    let data = serde_json::to_vec_pretty(s)?;
-    fs::write(&tmp, data)?;
-    atomic_rename(&tmp, path)?;
-    Ok(())
+    fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    atomic_rename(&tmp, path)
 }
 
 pub(crate) fn atomic_rename(from: &Path, to: &Path) -> Result<()> {
@@ -69,3 +181,32 @@ pub(crate) fn atomic_rename(from: &Path, to: &Path) -> Result<()> {
     fs::rename(from, to)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeds_parse_as_decimal_or_hex() {
+        assert_eq!(parse_seed("12648430").unwrap(), 0xC0FFEE);
+        assert_eq!(parse_seed(" 0xC0FFEE ").unwrap(), 0xC0FFEE);
+        assert!(parse_seed("0").is_err());
+        assert!(parse_seed("0x").is_err());
+        assert!(parse_seed("18446744073709551616").is_err());
+    }
+
+    #[test]
+    fn unreadable_settings_are_an_error_not_the_defaults() {
+        let path = std::env::temp_dir().join(format!("tg-settings-{}.json", std::process::id()));
+        fs::remove_file(&path).ok();
+        assert_eq!(
+            load_settings(&path).unwrap().fps_cap,
+            Settings::default().fps_cap
+        );
+
+        fs::write(&path, "{ \"skin_name\": \"default\", ").unwrap();
+        let err = load_settings(&path);
+        fs::remove_file(&path).ok();
+        assert!(err.is_err());
+    }
+}
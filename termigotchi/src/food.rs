@@ -0,0 +1,181 @@
+use crate::config::write_json_atomic;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum FoodKind {
+    Snack,
+    Meal,
+    Treat,
+    Special,
+}
+
+impl FoodKind {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            FoodKind::Snack => "Snack",
+            FoodKind::Meal => "Meal",
+            FoodKind::Treat => "Treat",
+            FoodKind::Special => "Special",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct FoodItem {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) kind: FoodKind,
+    #[serde(default)]
+    pub(crate) hunger: f32,
+    #[serde(default)]
+    pub(crate) happiness: f32,
+    #[serde(default)]
+    pub(crate) health: f32,
+    #[serde(default)]
+    pub(crate) energy: f32,
+    #[serde(default)]
+    pub(crate) weight: f32,
+    #[serde(default)]
+    pub(crate) poop_chance: f32,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct FoodPrefs {
+    #[serde(default)]
+    pub(crate) favorites: Vec<String>,
+    #[serde(default)]
+    pub(crate) dislikes: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Taste {
+    Favorite,
+    Neutral,
+    Disliked,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct FoodCatalog {
+    pub(crate) foods: Vec<FoodItem>,
+    // keyed by Pet.species_id
+    #[serde(default)]
+    pub(crate) species: BTreeMap<String, FoodPrefs>,
+}
+
+impl FoodCatalog {
+    pub(crate) fn get(&self, id: &str) -> Option<&FoodItem> {
+        self.foods.iter().find(|f| f.id == id)
+    }
+
+    pub(crate) fn taste(&self, species_id: &str, food_id: &str) -> Taste {
+        let Some(prefs) = self.species.get(species_id) else {
+            return Taste::Neutral;
+        };
+        if prefs.favorites.iter().any(|f| f == food_id) {
+            Taste::Favorite
+        } else if prefs.dislikes.iter().any(|f| f == food_id) {
+            Taste::Disliked
+        } else {
+            Taste::Neutral
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        !self.foods.is_empty()
+            && self.foods.iter().all(|f| {
+                !f.id.is_empty()
+                    && f.hunger.is_finite()
+                    && f.happiness.is_finite()
+                    && f.health.is_finite()
+                    && f.energy.is_finite()
+                    && f.weight.is_finite()
+                    && f.poop_chance.is_finite()
+            })
+    }
+}
+
+// fx = [hunger, happiness, health, energy, weight, poop_chance]
+fn food(id: &str, name: &str, kind: FoodKind, fx: [f32; 6]) -> FoodItem {
+    let [hunger, happiness, health, energy, weight, poop_chance] = fx;
+    FoodItem {
+        id: id.to_string(),
+        name: name.to_string(),
+        kind,
+        hunger,
+        happiness,
+        health,
+        energy,
+        weight,
+        poop_chance,
+    }
+}
+
+impl Default for FoodCatalog {
+    fn default() -> Self {
+        #[rustfmt::skip]
+        let foods = vec![
+            food("kibble", "Kibble", FoodKind::Snack, [10.0, 2.0, 0.0, 0.0, 0.3, 0.15]),
+            food("apple", "Apple", FoodKind::Snack, [8.0, 3.0, 1.5, 0.0, 0.1, 0.10]),
+            food("rice", "Rice bowl", FoodKind::Meal, [24.0, 3.0, 1.0, 3.0, 0.8, 0.30]),
+            food("fish", "Grilled fish", FoodKind::Meal, [22.0, 5.0, 2.5, 2.0, 0.6, 0.30]),
+            food("broccoli", "Broccoli", FoodKind::Meal, [16.0, -2.0, 4.0, 0.0, 0.2, 0.25]),
+            food("cookie", "Cookie", FoodKind::Treat, [4.0, 12.0, -1.0, 2.0, 1.2, 0.10]),
+            food("cake", "Cake", FoodKind::Treat, [8.0, 18.0, -2.0, 4.0, 2.0, 0.20]),
+            food("vitamin", "Vitamin", FoodKind::Special, [0.0, -1.0, 10.0, 0.0, 0.0, 0.0]),
+            food("coffee", "Coffee", FoodKind::Special, [2.0, 2.0, -1.0, 20.0, 0.0, 0.05]),
+        ];
+
+        let prefs = |favorites: &[&str], dislikes: &[&str]| FoodPrefs {
+            favorites: favorites.iter().map(|s| s.to_string()).collect(),
+            dislikes: dislikes.iter().map(|s| s.to_string()).collect(),
+        };
+        let mut species = BTreeMap::new();
+        species.insert("default".to_string(), prefs(&["apple"], &["broccoli"]));
+        species.insert("tot".to_string(), prefs(&["rice"], &["coffee"]));
+        species.insert(
+            "sprig".to_string(),
+            prefs(&["apple", "broccoli"], &["cake"]),
+        );
+        species.insert("grub".to_string(), prefs(&["cookie"], &["broccoli"]));
+        species.insert("scout".to_string(), prefs(&["fish"], &["cookie"]));
+        species.insert("imp".to_string(), prefs(&["cake", "cookie"], &["broccoli"]));
+        species.insert("sprout".to_string(), prefs(&["apple"], &["coffee"]));
+        species.insert("mope".to_string(), prefs(&["cookie"], &["fish"]));
+        species.insert("lumen".to_string(), prefs(&["fish", "apple"], &["coffee"]));
+        species.insert("warden".to_string(), prefs(&["coffee"], &["cake"]));
+        species.insert("jester".to_string(), prefs(&["cake"], &["rice"]));
+        species.insert("grumble".to_string(), prefs(&["coffee"], &["apple"]));
+        species.insert("bloom".to_string(), prefs(&["rice", "apple"], &["coffee"]));
+        species.insert("wisp".to_string(), prefs(&["vitamin"], &["fish"]));
+        species.insert("sage".to_string(), prefs(&["rice"], &["coffee"]));
+        species.insert("husk".to_string(), prefs(&["cookie"], &["vitamin"]));
+
+        Self { foods, species }
+    }
+}
+
+// The built-in catalog, or `foods.json` in its place. A missing file is written out with
+// the built-ins so it can be edited; an unreadable or invalid one is an error.
+pub(crate) fn load_food_catalog(path: &Path) -> Result<FoodCatalog> {
+    match fs::read_to_string(path) {
+        Ok(s) => {
+            let catalog: FoodCatalog =
+                serde_json::from_str(&s).with_context(|| format!("parsing {}", path.display()))?;
+            if !catalog.is_valid() {
+                bail!(
+                    "in {}: needs at least one food, each with an id and finite effects",
+                    path.display()
+                );
+            }
+            Ok(catalog)
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let catalog = FoodCatalog::default();
+            let _ = write_json_atomic(path, &catalog);
+            Ok(catalog)
+        }
+        Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+    }
+}
@@ -0,0 +1,171 @@
+use crate::model::RNGState;
+use serde::{Deserialize, Serialize};
+
+// Reaction game timing, in sim ticks.
+const REACT_DELAY_MIN: u64 = 4;
+const REACT_DELAY_SPREAD: u64 = 7;
+const REACT_WINDOW: u64 = 3;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum MiniGameKind {
+    LeftRight,
+    HigherLower,
+    Reaction,
+}
+
+impl MiniGameKind {
+    pub(crate) const ALL: [MiniGameKind; 3] = [
+        MiniGameKind::LeftRight,
+        MiniGameKind::HigherLower,
+        MiniGameKind::Reaction,
+    ];
+
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            MiniGameKind::LeftRight => "Left or Right?",
+            MiniGameKind::HigherLower => "Higher or Lower?",
+            MiniGameKind::Reaction => "Quick Paws",
+        }
+    }
+
+    fn rounds(self) -> u32 {
+        match self {
+            MiniGameKind::LeftRight => 5,
+            MiniGameKind::HigherLower => 5,
+            MiniGameKind::Reaction => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum GameKey {
+    Left,
+    Right,
+    Up,
+    Down,
+    Press,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct MiniGame {
+    pub(crate) kind: MiniGameKind,
+    pub(crate) round: u32,
+    pub(crate) rounds: u32,
+    pub(crate) wins: u32,
+    // LeftRight: hidden side (0 = left, 1 = right); HigherLower: number on show
+    pub(crate) value: u32,
+    // Reaction: tick at which "NOW!" appears
+    pub(crate) go_tick: u64,
+    pub(crate) last: Option<bool>,
+    pub(crate) finished: bool,
+}
+
+impl MiniGame {
+    pub(crate) fn start(kind: MiniGameKind, rng: &mut RNGState, now: u64) -> Self {
+        let mut g = Self {
+            kind,
+            round: 0,
+            rounds: kind.rounds(),
+            wins: 0,
+            value: 0,
+            go_tick: 0,
+            last: None,
+            finished: false,
+        };
+        g.setup_round(rng, now);
+        if kind == MiniGameKind::HigherLower {
+            g.value = 1 + (rng.next_u64() % 9) as u32;
+        }
+        g
+    }
+
+    fn setup_round(&mut self, rng: &mut RNGState, now: u64) {
+        match self.kind {
+            MiniGameKind::LeftRight => self.value = (rng.next_u64() % 2) as u32,
+            MiniGameKind::HigherLower => {}
+            MiniGameKind::Reaction => {
+                self.go_tick = now + REACT_DELAY_MIN + rng.next_u64() % REACT_DELAY_SPREAD;
+            }
+        }
+    }
+
+    fn end_round(&mut self, won: bool, rng: &mut RNGState, now: u64) {
+        self.last = Some(won);
+        if won {
+            self.wins += 1;
+        }
+        self.round += 1;
+        if self.round >= self.rounds {
+            self.finished = true;
+        } else {
+            self.setup_round(rng, now);
+        }
+    }
+
+    // Returns true when this input finished the game.
+    pub(crate) fn input(&mut self, key: GameKey, rng: &mut RNGState, now: u64) -> bool {
+        if self.finished {
+            return false;
+        }
+        match (self.kind, key) {
+            (MiniGameKind::LeftRight, GameKey::Left | GameKey::Right) => {
+                let guess = if key == GameKey::Left { 0 } else { 1 };
+                self.end_round(guess == self.value, rng, now);
+            }
+            (MiniGameKind::HigherLower, GameKey::Up | GameKey::Down) => {
+                // a tie never counts, so re-draw it
+                let mut next = self.value;
+                while next == self.value {
+                    next = 1 + (rng.next_u64() % 9) as u32;
+                }
+                let won = (key == GameKey::Up) == (next > self.value);
+                self.value = next;
+                self.end_round(won, rng, now);
+            }
+            (MiniGameKind::Reaction, GameKey::Press) => {
+                // pressing before "NOW!" is a false start
+                let won = now >= self.go_tick && now - self.go_tick <= REACT_WINDOW;
+                self.end_round(won, rng, now);
+            }
+            _ => {}
+        }
+        self.finished
+    }
+
+    // Returns true when the clock finished the game.
+    pub(crate) fn tick(&mut self, rng: &mut RNGState, now: u64) -> bool {
+        if self.finished {
+            return false;
+        }
+        if self.kind == MiniGameKind::Reaction && now > self.go_tick + REACT_WINDOW {
+            self.end_round(false, rng, now);
+        }
+        self.finished
+    }
+
+    pub(crate) fn score(&self) -> f32 {
+        if self.rounds == 0 {
+            return 0.0;
+        }
+        self.wins as f32 / self.rounds as f32
+    }
+
+    pub(crate) fn prompt(&self, now: u64) -> String {
+        if self.finished {
+            return format!("Done! {} of {} — enter to finish", self.wins, self.rounds);
+        }
+        match self.kind {
+            MiniGameKind::LeftRight => "Which way will it hop? ← / →".to_string(),
+            MiniGameKind::HigherLower => {
+                format!("Number: {}  — next one ↑ higher or ↓ lower?", self.value)
+            }
+            MiniGameKind::Reaction => {
+                if now >= self.go_tick {
+                    "NOW! Press space!".to_string()
+                } else {
+                    "Wait for it...".to_string()
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use crate::journal::{JournalEntry, JournalEvent};
+use crate::model::{GameState, LifeStage, Rules};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+const KINDS: [&str; 4] = ["attention", "sick", "evolve", "death"];
+
+// Shell commands run when something happens to a pet, keyed by event. Each one gets the
+// details as TERMIGOTCHI_* environment variables and as a JSON object on stdin.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Hooks {
+    #[serde(default = "default_enabled")]
+    pub(crate) enabled: bool,
+    #[serde(default)]
+    pub(crate) attention: Option<String>,
+    #[serde(default)]
+    pub(crate) sick: Option<String>,
+    #[serde(default)]
+    pub(crate) evolve: Option<String>,
+    #[serde(default)]
+    pub(crate) death: Option<String>,
+    // a hook runs at most once per this many seconds; what happens in between is counted
+    #[serde(default = "default_min_interval_secs")]
+    pub(crate) min_interval_secs: u64,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_min_interval_secs() -> u64 {
+    300
+}
+
+impl Default for Hooks {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            attention: None,
+            sick: None,
+            evolve: None,
+            death: None,
+            min_interval_secs: default_min_interval_secs(),
+        }
+    }
+}
+
+impl Hooks {
+    fn command(&self, kind: &str) -> Option<&str> {
+        match kind {
+            "attention" => self.attention.as_deref(),
+            "sick" => self.sick.as_deref(),
+            "evolve" => self.evolve.as_deref(),
+            "death" => self.death.as_deref(),
+            _ => None,
+        }
+        .filter(|c| !c.trim().is_empty())
+    }
+
+    pub(crate) fn configured(&self) -> usize {
+        KINDS.iter().filter(|k| self.command(k).is_some()).count()
+    }
+}
+
+// Rate-limit bookkeeping, kept in the save so back-to-back headless runs share it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub(crate) struct HookLog {
+    // wall time each hook last ran
+    pub(crate) fired: BTreeMap<String, DateTime<Utc>>,
+    // events held back since then; reported with the next run
+    pub(crate) missed: BTreeMap<String, u32>,
+    // the latest of those, run once the interval has passed even if nothing else happens
+    #[serde(default)]
+    pub(crate) held: BTreeMap<String, LifeEvent>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub(crate) struct LifeEvent {
+    pub(crate) event: &'static str,
+    pub(crate) pet: String,
+    pub(crate) species: String,
+    pub(crate) stage: LifeStage,
+    pub(crate) age_secs: u64,
+    pub(crate) detail: String,
+    pub(crate) journal: JournalEvent,
+}
+
+// A LifeEvent as read back from the save, with `event` not yet matched to a hook.
+#[derive(Deserialize)]
+struct StoredEvent {
+    event: String,
+    pet: String,
+    species: String,
+    stage: LifeStage,
+    age_secs: u64,
+    detail: String,
+    journal: JournalEvent,
+}
+
+impl<'de> Deserialize<'de> for LifeEvent {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> std::result::Result<Self, D::Error> {
+        let s = StoredEvent::deserialize(d)?;
+        let event = KINDS
+            .into_iter()
+            .find(|k| *k == s.event)
+            .ok_or_else(|| de::Error::custom(format!("unknown hook '{}'", s.event)))?;
+        Ok(LifeEvent {
+            event,
+            pet: s.pet,
+            species: s.species,
+            stage: s.stage,
+            age_secs: s.age_secs,
+            detail: s.detail,
+            journal: s.journal,
+        })
+    }
+}
+
+// Which hook an event sets off, if any.
+pub(crate) fn hook_kind(event: &JournalEvent) -> Option<&'static str> {
+    match event {
+        JournalEvent::AttentionCall => Some("attention"),
+        JournalEvent::FellSick => Some("sick"),
+        JournalEvent::Evolved { .. } => Some("evolve"),
+        JournalEvent::Died { .. } => Some("death"),
+        _ => None,
+    }
+}
+
+// Collect what the pets have been through since the last call, oldest first per pet.
+pub(crate) fn take_events(st: &mut GameState, rules: &Rules) -> Vec<LifeEvent> {
+    let mut out = Vec::new();
+    for pet in &mut st.pets {
+        for JournalEntry { tick, event, .. } in std::mem::take(&mut pet.outbox) {
+            let Some(kind) = hook_kind(&event) else {
+                continue;
+            };
+            out.push(LifeEvent {
+                event: kind,
+                pet: pet.name.clone(),
+                species: pet.species_id.clone(),
+                stage: pet.stage,
+                age_secs: tick * rules.tick_step_ms / 1000,
+                detail: event.describe(),
+                journal: event,
+            });
+        }
+    }
+    out
+}
+
+// The hooks to run now: for each kind, the latest event and how many it stands for. A
+// catch-up that saw dozens of attention calls runs the attention hook once. Events held
+// back by the rate limit run as soon as it allows, whether or not more have come in.
+fn plan<'a>(
+    hooks: &'a Hooks,
+    log: &mut HookLog,
+    events: Vec<LifeEvent>,
+    now: DateTime<Utc>,
+) -> Vec<(&'a str, LifeEvent, u32)> {
+    let mut latest: BTreeMap<&'static str, (LifeEvent, u32)> = BTreeMap::new();
+    for ev in events {
+        let count = latest.get(ev.event).map_or(0, |(_, n)| *n) + 1;
+        latest.insert(ev.event, (ev, count));
+    }
+    for (_, ev) in std::mem::take(&mut log.held) {
+        latest.entry(ev.event).or_insert((ev, 0));
+    }
+
+    let interval = ChronoDuration::seconds(hooks.min_interval_secs.min(i64::MAX as u64) as i64);
+    let mut due = Vec::new();
+    for (kind, (ev, count)) in latest {
+        let Some(cmd) = hooks.command(kind) else {
+            continue;
+        };
+        let ready = log.fired.get(kind).is_none_or(|&at| now - at >= interval);
+        let missed = log.missed.entry(kind.to_string()).or_default();
+        if ready {
+            due.push((cmd, ev, count + *missed));
+            *missed = 0;
+            log.fired.insert(kind.to_string(), now);
+        } else {
+            *missed = missed.saturating_add(count);
+            log.held.insert(kind.to_string(), ev);
+        }
+    }
+    log.missed.retain(|_, n| *n > 0);
+    due
+}
+
+// Run whatever hooks the events call for. Hooks run in the background with their output
+// discarded; a failure to start one is returned, never fatal.
+pub(crate) fn run_hooks(
+    hooks: &Hooks,
+    log: &mut HookLog,
+    events: Vec<LifeEvent>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    if !hooks.enabled || (events.is_empty() && log.held.is_empty()) {
+        return Ok(());
+    }
+    let mut first_err = None;
+    for (cmd, ev, count) in plan(hooks, log, events, now) {
+        if let Err(e) = spawn(cmd, &ev, count) {
+            first_err.get_or_insert(e.context(format!("{} hook failed", ev.event)));
+        }
+    }
+    first_err.map_or(Ok(()), Err)
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    #[serde(flatten)]
+    event: &'a LifeEvent,
+    count: u32,
+}
+
+fn spawn(cmd: &str, ev: &LifeEvent, count: u32) -> Result<()> {
+    let payload = serde_json::to_vec(&Payload { event: ev, count })?;
+    let mut shell = if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.arg("/C");
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.arg("-c");
+        c
+    };
+    let mut child = shell
+        .arg(cmd)
+        .env("TERMIGOTCHI_EVENT", ev.event)
+        .env("TERMIGOTCHI_PET", &ev.pet)
+        .env("TERMIGOTCHI_SPECIES", &ev.species)
+        .env("TERMIGOTCHI_STAGE", format!("{:?}", ev.stage))
+        .env("TERMIGOTCHI_AGE_SECS", ev.age_secs.to_string())
+        .env("TERMIGOTCHI_DETAIL", &ev.detail)
+        .env("TERMIGOTCHI_COUNT", count.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("could not run '{cmd}'"))?;
+
+    // the payload is far smaller than a pipe buffer, so this never waits on the hook;
+    // a hook that ignores stdin just closes it early
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(&payload);
+    }
+    // reap it in the background so a slow hook never holds up the game
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DeathCause;
+
+    fn event(kind: JournalEvent) -> LifeEvent {
+        LifeEvent {
+            event: hook_kind(&kind).unwrap(),
+            pet: "Mochi".to_string(),
+            species: "default".to_string(),
+            stage: LifeStage::Baby,
+            age_secs: 0,
+            detail: kind.describe(),
+            journal: kind,
+        }
+    }
+
+    #[test]
+    fn hooks_are_coalesced_and_rate_limited() {
+        let hooks = Hooks {
+            attention: Some("notify-send hungry".to_string()),
+            sick: Some("notify-send sick".to_string()),
+            ..Hooks::default()
+        };
+        let mut log = HookLog::default();
+        let t0 = DateTime::<Utc>::default();
+
+        // a long catch-up: many calls, one sickness, one evolution without a hook
+        let mut events: Vec<LifeEvent> = (0..40)
+            .map(|_| event(JournalEvent::AttentionCall))
+            .collect();
+        events.push(event(JournalEvent::FellSick));
+        events.push(event(JournalEvent::Evolved {
+            stage: LifeStage::Child,
+            species: "default".to_string(),
+        }));
+        let due = plan(&hooks, &mut log, events, t0);
+        let counts: Vec<(&str, u32)> = due.iter().map(|(_, ev, n)| (ev.event, *n)).collect();
+        assert_eq!(counts, [("attention", 40), ("sick", 1)]);
+
+        // within the interval the call is held back and counted
+        let soon = t0 + ChronoDuration::seconds(60);
+        assert!(plan(
+            &hooks,
+            &mut log,
+            vec![event(JournalEvent::AttentionCall)],
+            soon
+        )
+        .is_empty());
+        assert_eq!(log.missed.get("attention"), Some(&1));
+
+        // once it has passed, the next call reports the one that was missed
+        let later = t0 + ChronoDuration::seconds(hooks.min_interval_secs as i64);
+        let due = plan(
+            &hooks,
+            &mut log,
+            vec![event(JournalEvent::AttentionCall)],
+            later,
+        );
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].2, 2);
+        assert!(log.missed.is_empty());
+    }
+
+    #[test]
+    fn held_back_events_fire_without_a_follow_up() {
+        let hooks = Hooks {
+            death: Some("notify-send gone".to_string()),
+            ..Hooks::default()
+        };
+        let mut log = HookLog::default();
+        let t0 = DateTime::<Utc>::default();
+        let died = || {
+            event(JournalEvent::Died {
+                cause: DeathCause::Illness,
+            })
+        };
+
+        assert_eq!(plan(&hooks, &mut log, vec![died()], t0).len(), 1);
+        // a second pet dies a minute later: held back
+        let soon = t0 + ChronoDuration::seconds(60);
+        assert!(plan(&hooks, &mut log, vec![died()], soon).is_empty());
+        assert!(plan(&hooks, &mut log, Vec::new(), soon).is_empty());
+
+        // nothing new happens, but the held-back death still runs its hook once allowed,
+        // and it survives a save in between
+        let saved = serde_json::to_string(&log).unwrap();
+        let mut log: HookLog = serde_json::from_str(&saved).unwrap();
+        let later = t0 + ChronoDuration::seconds(hooks.min_interval_secs as i64);
+        let due = plan(&hooks, &mut log, Vec::new(), later);
+        let counts: Vec<(&str, u32)> = due.iter().map(|(_, ev, n)| (ev.event, *n)).collect();
+        assert_eq!(counts, [("death", 1)]);
+        assert!(log.missed.is_empty() && log.held.is_empty());
+        assert!(plan(&hooks, &mut log, Vec::new(), later).is_empty());
+    }
+}
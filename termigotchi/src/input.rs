@@ -1,3 +1,4 @@
+use crate::games::GameKey;
 use crate::model::Scene;
 use crate::sim::PlayerAction;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
@@ -15,19 +16,16 @@ pub(crate) fn collect_input_nonblocking(max_frame_time: Duration) -> anyhow::Res
     // poll with a tiny timeout so we stay responsive
     let timeout = std::cmp::min(Duration::from_millis(1), max_frame_time);
     while event::poll(timeout)? {
-        match event::read()? {
-            Event::Key(k) => {
-                if k.kind == KeyEventKind::Press || k.kind == KeyEventKind::Repeat {
-                    out.push(InputEvent {
-                        key: k.code,
-                        mods: k.modifiers,
-                    });
-                    if out.len() >= 32 {
-                        break;
-                    }
+        if let Event::Key(k) = event::read()? {
+            if k.kind == KeyEventKind::Press || k.kind == KeyEventKind::Repeat {
+                out.push(InputEvent {
+                    key: k.code,
+                    mods: k.modifiers,
+                });
+                if out.len() >= 32 {
+                    break;
                 }
             }
-            _ => {}
         }
     }
     Ok(out)
@@ -50,6 +48,18 @@ pub(crate) fn map_event_to_action(scene: &Scene, ev: InputEvent) -> Option<Playe
         };
     }
 
+    if matches!(scene, Scene::SeedEdit) {
+        return match ev.key {
+            KeyCode::Enter => Some(PlayerAction::SeedEditCommit),
+            KeyCode::Esc => Some(PlayerAction::SeedEditClose),
+            KeyCode::Backspace => Some(PlayerAction::SeedEditBackspace),
+            KeyCode::Char(ch) if ch.is_ascii_hexdigit() || ch == 'x' || ch == 'X' => {
+                Some(PlayerAction::SeedEditChar(ch))
+            }
+            _ => None,
+        };
+    }
+
     // Global
     if matches!(ev.key, KeyCode::Char('p') | KeyCode::Char('P'))
         && ev.mods.contains(KeyModifiers::CONTROL)
@@ -65,32 +75,72 @@ pub(crate) fn map_event_to_action(scene: &Scene, ev: InputEvent) -> Option<Playe
 
     match scene {
         Scene::Main => match ev.key {
-            KeyCode::Char('f') | KeyCode::Char('F') => Some(PlayerAction::Feed("kibble")),
+            KeyCode::Char('f') | KeyCode::Char('F') => Some(PlayerAction::FoodMenuOpen),
             KeyCode::Char('p') | KeyCode::Char('P') => Some(PlayerAction::PlayAny),
             KeyCode::Char('c') | KeyCode::Char('C') => Some(PlayerAction::Clean),
             KeyCode::Char('m') | KeyCode::Char('M') => Some(PlayerAction::Medicine),
             KeyCode::Char('s') | KeyCode::Char('S') => Some(PlayerAction::SleepToggle),
             KeyCode::Char('d') | KeyCode::Char('D') => Some(PlayerAction::Discipline),
+            KeyCode::Char('l') | KeyCode::Char('L') => Some(PlayerAction::LightsToggle),
+            KeyCode::Char('j') | KeyCode::Char('J') => Some(PlayerAction::HistoryToggle),
+            KeyCode::Char('g') | KeyCode::Char('G') => Some(PlayerAction::GraveyardToggle),
+            KeyCode::Char('a') | KeyCode::Char('A') => Some(PlayerAction::Adopt),
+            KeyCode::Left => Some(PlayerAction::PetSelect(-1)),
+            KeyCode::Right => Some(PlayerAction::PetSelect(1)),
             KeyCode::Tab => Some(PlayerAction::SettingsOpen),
             _ => None,
         },
         Scene::Settings => match ev.key {
             KeyCode::Up => Some(PlayerAction::SettingsMove(-1)),
             KeyCode::Down => Some(PlayerAction::SettingsMove(1)),
+            KeyCode::Left => Some(PlayerAction::SettingsAdjust(-1)),
+            KeyCode::Right => Some(PlayerAction::SettingsAdjust(1)),
             KeyCode::Enter => Some(PlayerAction::SettingsToggle),
             KeyCode::Esc => Some(PlayerAction::Back),
             KeyCode::Tab => Some(PlayerAction::Back),
             _ => None,
         },
+        Scene::Food => match ev.key {
+            KeyCode::Up => Some(PlayerAction::FoodMove(-1)),
+            KeyCode::Down => Some(PlayerAction::FoodMove(1)),
+            KeyCode::Enter => Some(PlayerAction::FoodPick),
+            KeyCode::Char('f') | KeyCode::Char('F') => Some(PlayerAction::Back),
+            _ => None,
+        },
+        Scene::Game(_) => match ev.key {
+            KeyCode::Left => Some(PlayerAction::GameInput(GameKey::Left)),
+            KeyCode::Right => Some(PlayerAction::GameInput(GameKey::Right)),
+            KeyCode::Up => Some(PlayerAction::GameInput(GameKey::Up)),
+            KeyCode::Down => Some(PlayerAction::GameInput(GameKey::Down)),
+            KeyCode::Char(' ') | KeyCode::Enter => Some(PlayerAction::GameInput(GameKey::Press)),
+            _ => None,
+        },
+        Scene::History => match ev.key {
+            KeyCode::Up => Some(PlayerAction::HistoryScroll(-1)),
+            KeyCode::Down => Some(PlayerAction::HistoryScroll(1)),
+            KeyCode::PageUp => Some(PlayerAction::HistoryScroll(-10)),
+            KeyCode::PageDown => Some(PlayerAction::HistoryScroll(10)),
+            KeyCode::Char('j') | KeyCode::Char('J') => Some(PlayerAction::HistoryToggle),
+            _ => None,
+        },
+        Scene::Graveyard => match ev.key {
+            KeyCode::Up => Some(PlayerAction::GraveyardMove(-1)),
+            KeyCode::Down => Some(PlayerAction::GraveyardMove(1)),
+            KeyCode::Char('g') | KeyCode::Char('G') => Some(PlayerAction::GraveyardToggle),
+            _ => None,
+        },
         Scene::Help => match ev.key {
             KeyCode::Esc => Some(PlayerAction::Back),
             _ => None,
         },
-        Scene::Dead => match ev.key {
+        Scene::Dead | Scene::Farewell => match ev.key {
             KeyCode::Char('n') | KeyCode::Char('N') => Some(PlayerAction::NewGame),
+            KeyCode::Char('g') | KeyCode::Char('G') => Some(PlayerAction::GraveyardToggle),
+            KeyCode::Left => Some(PlayerAction::PetSelect(-1)),
+            KeyCode::Right => Some(PlayerAction::PetSelect(1)),
             _ => None,
         },
-        Scene::Rename => None,
+        Scene::Rename | Scene::SeedEdit => None,
         Scene::Recap(_) => None,
     }
 }
@@ -0,0 +1,169 @@
+use crate::model::{DeathCause, LifeStage, Meters};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+const MAX_ENTRIES: usize = 500;
+const MAX_SAMPLES: usize = 720;
+// one sample per this many ticks to start with (~1 min at the classic tick rate)
+const FIRST_SAMPLE_EVERY: u64 = 120;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum JournalEvent {
+    Evolved {
+        stage: LifeStage,
+        species: String,
+    },
+    FellSick,
+    Cured,
+    AttentionCall,
+    IgnoredCall,
+    Fed {
+        food: String,
+    },
+    Played {
+        game: String,
+        wins: u32,
+        rounds: u32,
+    },
+    Cleaned,
+    Disciplined,
+    Slept,
+    Woke,
+    KeptUp,
+    Renamed {
+        name: String,
+    },
+    Away {
+        secs: u64,
+        attention_calls: u32,
+        became_sick: bool,
+    },
+    Died {
+        cause: DeathCause,
+    },
+}
+
+impl JournalEvent {
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            JournalEvent::Evolved { stage, species } => {
+                format!("Grew into a {species} ({stage:?})")
+            }
+            JournalEvent::FellSick => "Fell sick".to_string(),
+            JournalEvent::Cured => "Took medicine".to_string(),
+            JournalEvent::AttentionCall => "Called for attention".to_string(),
+            JournalEvent::IgnoredCall => "Call went unanswered".to_string(),
+            JournalEvent::Fed { food } => format!("Ate {food}"),
+            JournalEvent::Played { game, wins, rounds } => {
+                format!("Played {game} ({wins}/{rounds})")
+            }
+            JournalEvent::Cleaned => "Got cleaned up".to_string(),
+            JournalEvent::Disciplined => "Was disciplined".to_string(),
+            JournalEvent::Slept => "Went to sleep".to_string(),
+            JournalEvent::Woke => "Woke up".to_string(),
+            JournalEvent::KeptUp => "Stayed up past bedtime".to_string(),
+            JournalEvent::Renamed { name } => format!("Renamed to {name}"),
+            JournalEvent::Away {
+                secs,
+                attention_calls,
+                became_sick,
+            } => format!(
+                "Alone {}, {attention_calls} calls{}",
+                format_secs(*secs),
+                if *became_sick { ", got sick" } else { "" }
+            ),
+            JournalEvent::Died { cause } => match cause {
+                DeathCause::Illness => "Passed away from illness".to_string(),
+                DeathCause::OldAge => "Passed away of old age".to_string(),
+            },
+        }
+    }
+}
+
+// Short age/duration label: "42s", "7m", "3h 05m", "2d 04h".
+pub(crate) fn format_secs(secs: u64) -> String {
+    let (d, h, m) = (secs / 86_400, secs / 3600 % 24, secs / 60 % 60);
+    if d > 0 {
+        format!("{d}d {h:02}h")
+    } else if h > 0 {
+        format!("{h}h {m:02}m")
+    } else if m > 0 {
+        format!("{m}m")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct JournalEntry {
+    // pet age in ticks when it happened
+    pub(crate) tick: u64,
+    // the sim's wall clock then; missing from entries saved before it was kept
+    #[serde(default)]
+    pub(crate) at: Option<DateTime<Utc>>,
+    pub(crate) event: JournalEvent,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub(crate) struct MeterSample {
+    pub(crate) tick: u64,
+    pub(crate) hunger: f32,
+    pub(crate) happiness: f32,
+    pub(crate) health: f32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Journal {
+    pub(crate) entries: VecDeque<JournalEntry>,
+    pub(crate) samples: Vec<MeterSample>,
+    pub(crate) sample_every: u64,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            samples: Vec::new(),
+            sample_every: FIRST_SAMPLE_EVERY,
+        }
+    }
+}
+
+impl Journal {
+    pub(crate) fn log(&mut self, tick: u64, at: DateTime<Utc>, event: JournalEvent) {
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(JournalEntry {
+            tick,
+            at: Some(at),
+            event,
+        });
+    }
+
+    // The whole life stays charted: when the buffer fills, every other sample is
+    // dropped and the interval doubles.
+    pub(crate) fn maybe_sample(&mut self, tick: u64, m: &Meters) {
+        if !tick.is_multiple_of(self.sample_every.max(1)) {
+            return;
+        }
+        if self.samples.len() >= MAX_SAMPLES {
+            let mut i = 0;
+            self.samples.retain(|_| {
+                i += 1;
+                i % 2 == 1
+            });
+            self.sample_every = self.sample_every.saturating_mul(2);
+            if !tick.is_multiple_of(self.sample_every) {
+                return;
+            }
+        }
+        self.samples.push(MeterSample {
+            tick,
+            hunger: m.hunger,
+            happiness: m.happiness,
+            health: m.health,
+        });
+    }
+}
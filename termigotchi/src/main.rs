@@ -1,13 +1,26 @@
 mod app;
+mod cli;
 mod config;
+mod food;
+mod games;
+mod hooks;
 mod input;
+mod journal;
 mod model;
 mod render;
+mod replay;
+mod rules;
 mod sim;
+mod skin;
 mod storage;
 
 use anyhow::Result;
+use clap::Parser;
 
 fn main() -> Result<()> {
-    app::run()
+    let cli = cli::Cli::parse();
+    match cli.command {
+        Some(cmd) => cli::run_command(cmd, cli.pet.as_deref()),
+        None => app::run(cli.record.as_deref()),
+    }
 }
@@ -1,8 +1,12 @@
+use crate::games::MiniGame;
+use crate::hooks::HookLog;
+use crate::journal::{Journal, JournalEntry};
+use crate::rules::DEFAULT_PRESET;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
-pub(crate) const SAVE_VERSION: u32 = 1;
+pub(crate) const SAVE_VERSION: u32 = 4;
 pub(crate) const GAME_VERSION: u32 = 1;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,8 +15,14 @@ pub(crate) enum Scene {
     Settings,
     Help,
     Rename,
+    SeedEdit,
+    Food,
+    Game(MiniGame),
     Recap(CatchupSummary),
+    History,
     Dead,
+    Farewell,
+    Graveyard,
 }
 
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
@@ -25,7 +35,18 @@ pub(crate) enum LifeStage {
     Elder,
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+impl LifeStage {
+    pub(crate) const ALL: [LifeStage; 6] = [
+        LifeStage::Egg,
+        LifeStage::Baby,
+        LifeStage::Child,
+        LifeStage::Teen,
+        LifeStage::Adult,
+        LifeStage::Elder,
+    ];
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub(crate) enum Mood {
     Happy,
     Okay,
@@ -63,7 +84,7 @@ impl Default for Meters {
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct Flags {
     pub(crate) sleeping: bool,
     pub(crate) sick: bool,
@@ -71,22 +92,12 @@ pub(crate) struct Flags {
     pub(crate) attention_call: bool,
     pub(crate) has_poop: bool,
     pub(crate) dead: bool,
+    // awake past bedtime tonight
+    #[serde(default)]
+    pub(crate) kept_up: bool,
 }
 
-impl Default for Flags {
-    fn default() -> Self {
-        Self {
-            sleeping: false,
-            sick: false,
-            dirty: false,
-            attention_call: false,
-            has_poop: false,
-            dead: false,
-        }
-    }
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub(crate) struct History {
     pub(crate) neglected_events: u32,
     pub(crate) play_events: u32,
@@ -96,19 +107,26 @@ pub(crate) struct History {
     pub(crate) sickness_events: u32,
 }
 
-impl Default for History {
-    fn default() -> Self {
-        Self {
-            neglected_events: 0,
-            play_events: 0,
-            feed_events: 0,
-            clean_events: 0,
-            discipline_events: 0,
-            sickness_events: 0,
+impl History {
+    // Counts accumulated since an earlier snapshot.
+    pub(crate) fn since(&self, earlier: &History) -> History {
+        History {
+            neglected_events: self.neglected_events.saturating_sub(earlier.neglected_events),
+            play_events: self.play_events.saturating_sub(earlier.play_events),
+            feed_events: self.feed_events.saturating_sub(earlier.feed_events),
+            clean_events: self.clean_events.saturating_sub(earlier.clean_events),
+            discipline_events: self.discipline_events.saturating_sub(earlier.discipline_events),
+            sickness_events: self.sickness_events.saturating_sub(earlier.sickness_events),
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum DeathCause {
+    Illness,
+    OldAge,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct Pet {
     pub(crate) name: String,
@@ -119,10 +137,45 @@ pub(crate) struct Pet {
     pub(crate) meters: Meters,
     pub(crate) flags: Flags,
     pub(crate) history: History,
+    // History snapshot taken when the current stage began
+    #[serde(default)]
+    pub(crate) stage_start: History,
+    // set on reaching Elder; 0 = not yet known
+    #[serde(default)]
+    pub(crate) lifespan_ticks: u64,
+    #[serde(default)]
+    pub(crate) cause_of_death: Option<DeathCause>,
+    #[serde(default)]
+    pub(crate) journal: Journal,
+    // each pet draws from its own stream, so housemates never shift each other's luck
+    pub(crate) rng: RNGState,
+    pub(crate) last_action_at_tick: u64,
+    #[serde(default)]
+    pub(crate) attention_at_tick: u64,
+    // events still to be handed to the hooks
+    #[serde(skip)]
+    pub(crate) outbox: Vec<JournalEntry>,
+    // 1 for a first egg, one more for each egg that inherits from the pet before it
+    #[serde(default = "first_generation")]
+    pub(crate) generation: u32,
+    // multipliers on the stage decay rates, handed down from the predecessor
+    #[serde(default = "neutral_constitution")]
+    pub(crate) constitution: DecayRates,
+    // already has a memorial in the graveyard
+    #[serde(default)]
+    pub(crate) remembered: bool,
+}
+
+fn first_generation() -> u32 {
+    1
+}
+
+fn neutral_constitution() -> DecayRates {
+    DecayRates::NEUTRAL
 }
 
 impl Pet {
-    pub(crate) fn new_default() -> Self {
+    pub(crate) fn new(seed: u64) -> Self {
         Self {
             name: "Mochi".to_string(),
             species_id: "default".to_string(),
@@ -132,6 +185,24 @@ impl Pet {
             meters: Meters::default(),
             flags: Flags::default(),
             history: History::default(),
+            stage_start: History::default(),
+            lifespan_ticks: 0,
+            cause_of_death: None,
+            journal: Journal::default(),
+            rng: RNGState::new(seed),
+            last_action_at_tick: 0,
+            attention_at_tick: 0,
+            outbox: Vec::new(),
+            generation: first_generation(),
+            constitution: DecayRates::NEUTRAL,
+            remembered: false,
+        }
+    }
+
+    pub(crate) fn death_scene(&self) -> Scene {
+        match self.cause_of_death {
+            Some(DeathCause::OldAge) => Scene::Farewell,
+            _ => Scene::Dead,
         }
     }
 }
@@ -150,6 +221,15 @@ impl RNGState {
         }
     }
 
+    // Seed for the `n`th egg of a household started from `base`.
+    pub(crate) fn derive_seed(base: u64, n: u64) -> u64 {
+        let mut r = Self {
+            seed: base,
+            event_counter: n,
+        };
+        r.next_u64()
+    }
+
     pub(crate) fn next_u64(&mut self) -> u64 {
         // Counter-based SplitMix64: deterministic and cheap.
         let mut z = self
@@ -182,6 +262,25 @@ pub(crate) struct DecayRates {
     pub(crate) hygiene: f32,
 }
 
+impl DecayRates {
+    // as multipliers, these leave the rates unchanged
+    pub(crate) const NEUTRAL: DecayRates = DecayRates {
+        hunger: 1.0,
+        happiness: 1.0,
+        energy: 1.0,
+        hygiene: 1.0,
+    };
+
+    pub(crate) fn scaled(self, by: DecayRates) -> DecayRates {
+        DecayRates {
+            hunger: self.hunger * by.hunger,
+            happiness: self.happiness * by.happiness,
+            energy: self.energy * by.energy,
+            hygiene: self.hygiene * by.hygiene,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct Rules {
     pub(crate) tick_step_ms: u64,     // 250ms typical
@@ -189,6 +288,20 @@ pub(crate) struct Rules {
     pub(crate) catchup_max_secs: i64, // 7 days typical
     pub(crate) meter_rate_scale: f32, // 1.0 = normal, lower = slower stat changes
     pub(crate) decay_by_stage: BTreeMap<LifeStage, DecayRates>,
+    // happiness/sec each awake pet gains from a happy housemate (or loses to a miserable one)
+    #[serde(default = "default_companionship")]
+    pub(crate) companionship: f32,
+    // decay multiplier while asleep at night under a schedule; 1.0 = no overnight slowdown
+    #[serde(default = "default_night_metabolism")]
+    pub(crate) night_metabolism: f32,
+}
+
+fn default_companionship() -> f32 {
+    0.3
+}
+
+fn default_night_metabolism() -> f32 {
+    0.1
 }
 
 impl Default for Rules {
@@ -255,6 +368,8 @@ impl Default for Rules {
             catchup_max_secs: 7 * 24 * 3600,
             meter_rate_scale: 0.02,
             decay_by_stage,
+            companionship: default_companionship(),
+            night_metabolism: default_night_metabolism(),
         }
     }
 }
@@ -262,86 +377,239 @@ impl Default for Rules {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct GameState {
     pub(crate) version: u32,
-    pub(crate) pet: Pet,
+    // never empty; care actions and the pet view go to `pets[selected]`
+    pub(crate) pets: Vec<Pet>,
+    pub(crate) selected: usize,
+    // eggs hatched in this household so far, for deriving new seeds
+    #[serde(default)]
+    pub(crate) hatched: u64,
     pub(crate) scene: Scene,
-    pub(crate) rng: RNGState,
     pub(crate) sim_ticks: u64,
-    pub(crate) last_action_at_tick: u64,
     #[serde(default)]
     pub(crate) settings_cursor: usize,
     #[serde(default)]
     pub(crate) name_edit: String,
+    #[serde(default)]
+    pub(crate) seed_edit: String,
+    #[serde(default)]
+    pub(crate) food_cursor: usize,
+    // how many journal entries the History scene is scrolled back
+    #[serde(default)]
+    pub(crate) journal_scroll: usize,
+    #[serde(default = "default_rules_preset")]
+    pub(crate) rules_preset: String,
+    // The sim's wall clock: one tick_step per tick, set to the real time on load.
+    #[serde(default)]
+    pub(crate) clock: DateTime<Utc>,
+    #[serde(default)]
+    pub(crate) schedule: Option<Schedule>,
+    #[serde(default)]
+    pub(crate) lights_off: bool,
+    #[serde(default)]
+    pub(crate) hook_log: HookLog,
+    // every pet this household has lost, oldest first
+    #[serde(default)]
+    pub(crate) graveyard: Vec<Memorial>,
+    #[serde(default)]
+    pub(crate) graveyard_cursor: usize,
+}
+
+fn default_rules_preset() -> String {
+    DEFAULT_PRESET.to_string()
+}
+
+// Bedtime and wake-up on the player's wall clock. The UTC offset is captured when the
+// schedule is set, so the sim (and a replay of it) never asks the system for a timezone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Schedule {
+    pub(crate) bedtime_hour: u32,
+    pub(crate) wake_hour: u32,
+    pub(crate) utc_offset_secs: i32,
+}
+
+impl Schedule {
+    const DAY_MINS: i64 = 24 * 60;
+
+    // (minutes since bedtime, length of the night), both in 0..DAY_MINS
+    fn night_span(&self, t: DateTime<Utc>) -> (i64, i64) {
+        let now = (t.timestamp() + self.utc_offset_secs as i64).div_euclid(60);
+        let bed = (self.bedtime_hour % 24) as i64 * 60;
+        let wake = (self.wake_hour % 24) as i64 * 60;
+        (
+            (now - bed).rem_euclid(Self::DAY_MINS),
+            (wake - bed).rem_euclid(Self::DAY_MINS),
+        )
+    }
+
+    // Minutes since bedtime, or None during the day.
+    pub(crate) fn night_minutes(&self, t: DateTime<Utc>) -> Option<i64> {
+        let (since_bed, night_len) = self.night_span(t);
+        (since_bed < night_len).then_some(since_bed)
+    }
+
+    pub(crate) fn is_night(&self, t: DateTime<Utc>) -> bool {
+        self.night_minutes(t).is_some()
+    }
+
+    // How far through the current night (or day) `t` is, 0..1.
+    pub(crate) fn progress(&self, t: DateTime<Utc>) -> f32 {
+        let (since_bed, night_len) = self.night_span(t);
+        if since_bed < night_len {
+            since_bed as f32 / night_len as f32
+        } else {
+            (since_bed - night_len) as f32 / (Self::DAY_MINS - night_len) as f32
+        }
+    }
+}
+
+pub(crate) const HOUSEHOLD_MAX: usize = 4;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Memorial {
+    pub(crate) name: String,
+    pub(crate) species_id: String,
+    pub(crate) stage: LifeStage,
+    pub(crate) generation: u32,
+    pub(crate) age_secs: u64,
+    pub(crate) cause: Option<DeathCause>,
+    pub(crate) history: History,
+    pub(crate) died_at: DateTime<Utc>,
 }
 
 impl GameState {
     pub(crate) fn new(seed: u64) -> Self {
         Self {
             version: GAME_VERSION,
-            pet: Pet::new_default(),
+            pets: vec![Pet::new(seed)],
+            selected: 0,
+            hatched: 1,
             scene: Scene::Main,
-            rng: RNGState::new(seed),
             sim_ticks: 0,
-            last_action_at_tick: 0,
             settings_cursor: 0,
             name_edit: String::new(),
+            seed_edit: String::new(),
+            food_cursor: 0,
+            journal_scroll: 0,
+            rules_preset: default_rules_preset(),
+            clock: DateTime::<Utc>::default(),
+            schedule: None,
+            lights_off: false,
+            hook_log: HookLog::default(),
+            graveyard: Vec::new(),
+            graveyard_cursor: 0,
         }
     }
+
+    pub(crate) fn pet(&self) -> &Pet {
+        &self.pets[self.selected]
+    }
+
+    pub(crate) fn pet_mut(&mut self) -> &mut Pet {
+        &mut self.pets[self.selected]
+    }
+
+    pub(crate) fn death_scene(&self) -> Scene {
+        self.pet().death_scene()
+    }
+
+    pub(crate) fn is_night(&self) -> bool {
+        self.schedule.is_some_and(|s| s.is_night(self.clock))
+    }
+
+    pub(crate) fn all_dead(&self) -> bool {
+        self.pets.iter().all(|p| p.flags.dead)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct SaveFile {
     pub(crate) version: u32,
     pub(crate) last_seen_utc: DateTime<Utc>,
+    // rules in effect when saved; catch-up replays the absence under these
+    pub(crate) rules: Rules,
     pub(crate) state: GameState,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) struct CatchupSummary {
     pub(crate) ticks_simulated: u64,
+    // one per pet, in household order
+    pub(crate) pets: Vec<PetRecap>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct PetRecap {
+    pub(crate) name: String,
     pub(crate) became_sick: bool,
     pub(crate) attention_calls: u32,
     pub(crate) died: bool,
     pub(crate) hunger_min: f32,
     pub(crate) happiness_min: f32,
     pub(crate) health_min: f32,
+    // was calling on the previous tick, so each call is counted once
+    #[serde(skip)]
+    calling: bool,
 }
 
-impl CatchupSummary {
-    pub(crate) fn new() -> Self {
+impl PetRecap {
+    fn new(pet: &Pet) -> Self {
         Self {
-            ticks_simulated: 0,
+            name: pet.name.clone(),
             became_sick: false,
             attention_calls: 0,
             died: false,
             hunger_min: 100.0,
             happiness_min: 100.0,
             health_min: 100.0,
+            calling: pet.flags.attention_call,
         }
     }
 
     pub(crate) fn has_anything(&self) -> bool {
-        self.ticks_simulated > 0
-            && (self.became_sick
-                || self.attention_calls > 0
-                || self.died
-                || self.hunger_min < 40.0
-                || self.happiness_min < 40.0
-                || self.health_min < 60.0)
+        self.became_sick
+            || self.attention_calls > 0
+            || self.died
+            || self.hunger_min < 40.0
+            || self.happiness_min < 40.0
+            || self.health_min < 60.0
     }
 
-    pub(crate) fn record(&mut self, st: &GameState) {
-        self.ticks_simulated += 1;
-        self.hunger_min = self.hunger_min.min(st.pet.meters.hunger);
-        self.happiness_min = self.happiness_min.min(st.pet.meters.happiness);
-        self.health_min = self.health_min.min(st.pet.meters.health);
-        if st.pet.flags.sick {
+    fn record(&mut self, pet: &Pet) {
+        if pet.flags.dead && self.died {
+            return;
+        }
+        self.hunger_min = self.hunger_min.min(pet.meters.hunger);
+        self.happiness_min = self.happiness_min.min(pet.meters.happiness);
+        self.health_min = self.health_min.min(pet.meters.health);
+        if pet.flags.sick {
             self.became_sick = true;
         }
-        if st.pet.flags.attention_call {
+        if pet.flags.attention_call && !self.calling {
             self.attention_calls += 1;
         }
-        if st.pet.flags.dead {
+        self.calling = pet.flags.attention_call;
+        if pet.flags.dead {
             self.died = true;
         }
     }
 }
+
+impl CatchupSummary {
+    pub(crate) fn new(st: &GameState) -> Self {
+        Self {
+            ticks_simulated: 0,
+            pets: st.pets.iter().map(PetRecap::new).collect(),
+        }
+    }
+
+    pub(crate) fn has_anything(&self) -> bool {
+        self.ticks_simulated > 0 && self.pets.iter().any(PetRecap::has_anything)
+    }
+
+    pub(crate) fn record(&mut self, st: &GameState) {
+        self.ticks_simulated += 1;
+        for (recap, pet) in self.pets.iter_mut().zip(&st.pets) {
+            recap.record(pet);
+        }
+    }
+}
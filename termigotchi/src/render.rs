@@ -1,5 +1,10 @@
-use crate::config::Settings;
-use crate::model::{GameState, LifeStage, Mood, Scene};
+use crate::config::{Settings, SettingsItem};
+use crate::food::{FoodCatalog, Taste};
+use crate::games::{MiniGame, MiniGameKind};
+use crate::journal::{format_secs, Journal, MeterSample};
+use crate::model::{DeathCause, GameState, Mood, Scene, HOUSEHOLD_MAX};
+use crate::skin::{LayerShape, Skin};
+use chrono::Local;
 use crossterm::{
     cursor,
     execute, queue,
@@ -125,6 +130,38 @@ impl PixelCanvas {
             a: (out_a.clamp(0.0, 1.0) * 255.0 + 0.5) as u8,
         };
     }
+    // Darken everything drawn so far (lights out).
+    pub(crate) fn dim(&mut self, factor: f32) {
+        for p in &mut self.px {
+            p.r = (p.r as f32 * factor) as u8;
+            p.g = (p.g as f32 * factor) as u8;
+            p.b = (p.b as f32 * factor) as u8;
+        }
+    }
+    fn line(&mut self, from: (i32, i32), to: (i32, i32), p: Pixel) {
+        // Bresenham
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.blend_over(x, y, p);
+            if (x, y) == to {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
 }
 
 pub(crate) struct Terminal {
@@ -326,135 +363,114 @@ pub(crate) struct Renderer;
 impl Renderer {
     pub(crate) fn draw_pet(
         canvas: &mut PixelCanvas,
+        skin: &Skin,
         st: &GameState,
         viewport: Viewport,
         offset: (i32, i32),
     ) {
         let center_x = viewport.x + viewport.w / 2 + offset.0;
         let center_y = viewport.y + viewport.h / 2 + offset.1;
-
-        let mood_col = match st.pet.mood {
-            Mood::Happy => Pixel {
-                r: 140,
-                g: 240,
-                b: 200,
-                a: 220,
-            },
-            Mood::Okay => Pixel {
-                r: 150,
-                g: 170,
-                b: 240,
-                a: 210,
-            },
-            Mood::Sad => Pixel {
-                r: 130,
-                g: 150,
-                b: 220,
-                a: 200,
-            },
-            Mood::Angry => Pixel {
-                r: 255,
-                g: 90,
-                b: 90,
-                a: 225,
-            },
-            Mood::Sick => Pixel {
-                r: 140,
-                g: 240,
-                b: 130,
-                a: 220,
-            },
-            Mood::Sleepy => Pixel {
-                r: 160,
-                g: 140,
-                b: 255,
-                a: 210,
-            },
-            Mood::Bored => Pixel {
-                r: 200,
-                g: 200,
-                b: 200,
-                a: 205,
-            },
-        };
-
-        let radius = match st.pet.stage {
-            LifeStage::Egg => 10,
-            LifeStage::Baby => 14,
-            LifeStage::Child => 18,
-            LifeStage::Teen => 20,
-            LifeStage::Adult => 22,
-            LifeStage::Elder => 24,
-        };
-
-        for y in -radius..=radius {
-            for x in -radius..=radius {
-                let d2 = (x * x + y * y) as f32;
-                let r2 = (radius * radius) as f32;
-                if d2 > r2 {
-                    continue;
+        let mood_col = mood_color(st.pet().mood);
+
+        for layer in skin.visible_layers(st) {
+            let (ax, ay) = skin.anchor(st.pet().stage, &layer.anchor);
+            let (ax, ay) = (center_x + ax, center_y + ay);
+            let paint = |c: [u8; 4]| {
+                let p = Pixel {
+                    r: c[0],
+                    g: c[1],
+                    b: c[2],
+                    a: c[3],
+                };
+                if layer.mood_color {
+                    Pixel {
+                        a: ((p.a as u32 * mood_col.a as u32) / 255) as u8,
+                        ..mood_col
+                    }
+                } else {
+                    p
                 }
-                let dist = (d2 / r2).sqrt();
-                let t = 1.0 - dist;
-                let a = (mood_col.a as f32 * (0.3 + 0.7 * t)).clamp(0.0, 255.0) as u8;
-                canvas.blend_over(center_x + x, center_y + y, Pixel { a, ..mood_col });
-            }
-        }
+            };
 
-        let eye = Pixel {
-            r: 5,
-            g: 5,
-            b: 8,
-            a: 245,
-        };
-        if st.pet.flags.sleeping {
-            for x in -5..=5 {
-                canvas.blend_over(center_x - 6 + x, center_y - radius / 5, eye);
-                canvas.blend_over(center_x + 6 + x, center_y - radius / 5, eye);
-            }
-            for x in -4..=4 {
-                canvas.blend_over(center_x - 6 + x, center_y - radius / 5 + 1, eye);
-                canvas.blend_over(center_x + 6 + x, center_y - radius / 5 + 1, eye);
-            }
-        } else {
-            let left_x = center_x - radius / 3;
-            let right_x = center_x + radius / 3;
-            let eye_y = center_y - radius / 5;
-            for dy in -1..=1 {
-                for dx in -1..=1 {
-                    canvas.blend_over(left_x + dx, eye_y + dy, eye);
-                    canvas.blend_over(right_x + dx, eye_y + dy, eye);
+            match &layer.shape {
+                LayerShape::Disc { radius, color } => {
+                    let radius = *radius;
+                    let col = paint(*color);
+                    let r2 = (radius * radius).max(1) as f32;
+                    for y in -radius..=radius {
+                        for x in -radius..=radius {
+                            let d2 = (x * x + y * y) as f32;
+                            if d2 > r2 {
+                                continue;
+                            }
+                            let t = 1.0 - (d2 / r2).sqrt();
+                            let a = (col.a as f32 * (0.3 + 0.7 * t)).clamp(0.0, 255.0) as u8;
+                            canvas.blend_over(ax + x, ay + y, Pixel { a, ..col });
+                        }
+                    }
+                }
+                LayerShape::Sprite { rows, palette, .. } => {
+                    // sprites are centered on their anchor
+                    let h = rows.len() as i32;
+                    let w = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0) as i32;
+                    let (x0, y0) = (ax - w / 2, ay - h / 2);
+                    for (yy, row) in rows.iter().enumerate() {
+                        for (xx, ch) in row.chars().enumerate() {
+                            if let Some(c) = palette.get(&ch) {
+                                canvas.blend_over(x0 + xx as i32, y0 + yy as i32, paint(*c));
+                            }
+                        }
+                    }
                 }
             }
         }
+    }
+}
 
-        if st.pet.flags.sick {
-            let p = Pixel {
-                r: 140,
-                g: 255,
-                b: 160,
-                a: 200,
-            };
-            for i in 0..8 {
-                canvas.blend_over(center_x + radius - i, center_y - radius + i, p);
-            }
-        }
-
-        if st.pet.flags.attention_call {
-            let alert = Pixel {
-                r: 255,
-                g: 80,
-                b: 80,
-                a: 230,
-            };
-            let y = center_y - radius - 6;
-            for dy in 0..6 {
-                canvas.blend_over(center_x, y + dy, alert);
-            }
-            canvas.blend_over(center_x, y + 7, alert);
-            canvas.blend_over(center_x - 1, y + 7, alert);
-            canvas.blend_over(center_x + 1, y + 7, alert);
-        }
+fn mood_color(mood: Mood) -> Pixel {
+    match mood {
+        Mood::Happy => Pixel {
+            r: 140,
+            g: 240,
+            b: 200,
+            a: 220,
+        },
+        Mood::Okay => Pixel {
+            r: 150,
+            g: 170,
+            b: 240,
+            a: 210,
+        },
+        Mood::Sad => Pixel {
+            r: 130,
+            g: 150,
+            b: 220,
+            a: 200,
+        },
+        Mood::Angry => Pixel {
+            r: 255,
+            g: 90,
+            b: 90,
+            a: 225,
+        },
+        Mood::Sick => Pixel {
+            r: 140,
+            g: 240,
+            b: 130,
+            a: 220,
+        },
+        Mood::Sleepy => Pixel {
+            r: 160,
+            g: 140,
+            b: 255,
+            a: 210,
+        },
+        Mood::Bored => Pixel {
+            r: 200,
+            g: 200,
+            b: 200,
+            a: 205,
+        },
     }
 }
 
@@ -480,6 +496,237 @@ pub(crate) fn pet_bounce_offset_cells(st: &GameState) -> (i32, i32) {
     (x, y)
 }
 
+/* -----------------------------
+   Day/night sky (bedtime schedule)
+------------------------------ */
+
+const STAR: Pixel = Pixel {
+    r: 200,
+    g: 205,
+    b: 255,
+    a: 150,
+};
+const MOON: Pixel = Pixel {
+    r: 235,
+    g: 230,
+    b: 190,
+    a: 230,
+};
+const SUN: Pixel = Pixel {
+    r: 255,
+    g: 205,
+    b: 70,
+    a: 240,
+};
+
+// Where the sun or moon sits: across the viewport as the day (or night) goes by,
+// highest at its midpoint.
+fn sky_body_pos(progress: f32, vp: Viewport) -> (i32, i32) {
+    let x = vp.x + (vp.w as f32 * (0.1 + 0.8 * progress)) as i32;
+    let dip = (2.0 * progress - 1.0).powi(2);
+    let y = vp.y + vp.h / 10 + (vp.h as f32 * 0.2 * dip) as i32;
+    (x, y)
+}
+
+// Drawn behind the pet, only when a bedtime schedule is on.
+pub(crate) fn draw_sky(canvas: &mut PixelCanvas, st: &GameState, vp: Viewport) {
+    let Some(schedule) = st.schedule else {
+        return;
+    };
+    let (cx, cy) = sky_body_pos(schedule.progress(st.clock), vp);
+    let r = (vp.h / 14).clamp(3, 10);
+
+    if schedule.is_night(st.clock) {
+        // the same scatter every frame, over the top half of the view
+        for i in 0..48u32 {
+            let h = i.wrapping_mul(2_654_435_761).rotate_left(7) ^ 0x9E37_79B9;
+            let sx = vp.x + (h % vp.w.max(1) as u32) as i32;
+            let sy = vp.y + ((h >> 16) % (vp.h / 2).max(1) as u32) as i32;
+            canvas.blend_over(sx, sy, STAR);
+        }
+        // crescent: the disc minus one shifted toward the upper right
+        for y in -r..=r {
+            for x in -r..=r {
+                let inside = x * x + y * y <= r * r;
+                let (bx, by) = (x - r / 2, y + r / 3);
+                let bite = bx * bx + by * by <= r * r;
+                if inside && !bite {
+                    canvas.blend_over(cx + x, cy + y, MOON);
+                }
+            }
+        }
+    } else {
+        for y in -r..=r {
+            for x in -r..=r {
+                if x * x + y * y <= r * r {
+                    canvas.blend_over(cx + x, cy + y, SUN);
+                }
+            }
+        }
+        for k in 0..8 {
+            let a = k as f32 * std::f32::consts::FRAC_PI_4;
+            let (dx, dy) = (a.cos(), a.sin());
+            let from = (
+                cx + (dx * (r + 2) as f32) as i32,
+                cy + (dy * (r + 2) as f32) as i32,
+            );
+            let to = (
+                cx + (dx * (r + 5) as f32) as i32,
+                cy + (dy * (r + 5) as f32) as i32,
+            );
+            canvas.line(from, to, SUN);
+        }
+    }
+}
+
+// ASCII stand-in: a sun or moon glyph with a few stars along the top row.
+pub(crate) fn draw_sky_ascii(buf: &mut CellBuffer, st: &GameState, x: i32, w: i32) {
+    let Some(schedule) = st.schedule else {
+        return;
+    };
+    let bg = Color::Black;
+    let vp = Viewport { x, y: 0, w, h: 4 };
+    let (cx, _) = sky_body_pos(schedule.progress(st.clock), vp);
+    if schedule.is_night(st.clock) {
+        for sx in (x + 3..x + w).step_by(7) {
+            draw_text(buf, sx as u16, 2 + (sx % 3) as u16, ".", Color::Grey, bg);
+        }
+        draw_text(buf, cx as u16, 1, "(", Color::Yellow, bg);
+    } else {
+        draw_text(buf, cx as u16, 1, "O", Color::Yellow, bg);
+    }
+}
+
+// "Day", "Night" or "Night, lights off"; None without a schedule.
+fn sky_label(st: &GameState) -> Option<&'static str> {
+    st.schedule?;
+    Some(match (st.is_night(), st.lights_off) {
+        (false, false) => "Day",
+        (false, true) => "Day, lights off",
+        (true, false) => "Night",
+        (true, true) => "Night, lights off",
+    })
+}
+
+/* -----------------------------
+   Meter history charts (History scene)
+------------------------------ */
+
+type MeterPick = fn(&MeterSample) -> f32;
+
+const CHARTS: [(&str, MeterPick, Pixel); 3] = [
+    (
+        "Hunger",
+        |s| s.hunger,
+        Pixel {
+            r: 250,
+            g: 190,
+            b: 90,
+            a: 255,
+        },
+    ),
+    (
+        "Happy",
+        |s| s.happiness,
+        Pixel {
+            r: 140,
+            g: 240,
+            b: 200,
+            a: 255,
+        },
+    ),
+    (
+        "Health",
+        |s| s.health,
+        Pixel {
+            r: 255,
+            g: 110,
+            b: 120,
+            a: 255,
+        },
+    ),
+];
+
+// Charts sit below the title row, stacked in equal bands of cell rows (top, height).
+// The first row of each band holds its label.
+fn chart_bands(rows: i32) -> [(i32, i32); 3] {
+    let top = 2;
+    let band = ((rows - top - 1) / 3).max(3);
+    [0, 1, 2].map(|i| (top + i * band, band))
+}
+
+// Line charts in braille subpixels over the cell columns `x..x + w`.
+pub(crate) fn draw_meter_charts(canvas: &mut PixelCanvas, journal: &Journal, x: i32, w: i32) {
+    let rows = (canvas.h / 4) as i32;
+    let axis = Pixel {
+        r: 110,
+        g: 110,
+        b: 110,
+        a: 160,
+    };
+    let samples = &journal.samples;
+
+    for ((_, pick, col), (top, h)) in CHARTS.iter().zip(chart_bands(rows)) {
+        let px0 = x * 2 + 2;
+        let pw = (w * 2 - 4).max(2);
+        let py0 = (top + 1) * 4;
+        let ph = ((h - 1) * 4 - 2).max(2);
+
+        canvas.line((px0, py0 + ph), (px0 + pw - 1, py0 + ph), axis);
+        canvas.line((px0, py0), (px0, py0 + ph), axis);
+
+        if samples.len() < 2 {
+            continue;
+        }
+        let last = samples.len() as i32 - 1;
+        let point = |i: usize| {
+            let v = pick(&samples[i]).clamp(0.0, 100.0);
+            let x = px0 + i as i32 * (pw - 1) / last;
+            let y = py0 + ((100.0 - v) / 100.0 * (ph - 1) as f32).round() as i32;
+            (x, y)
+        };
+        for i in 1..samples.len() {
+            canvas.line(point(i - 1), point(i), *col);
+        }
+    }
+}
+
+// Labels for draw_meter_charts, drawn over the cells once the canvas is converted.
+pub(crate) fn draw_meter_chart_labels(
+    buf: &mut CellBuffer,
+    journal: &Journal,
+    x: u16,
+    tick_step_ms: u64,
+) {
+    let bg = Color::Black;
+    let samples = &journal.samples;
+
+    for ((name, pick, col), (top, _)) in CHARTS.iter().zip(chart_bands(buf.h as i32)) {
+        let label = match samples.last() {
+            Some(last) => {
+                let low = samples.iter().map(pick).fold(100.0f32, f32::min);
+                format!("{name} {:.0}  (low {:.0})", pick(last), low)
+            }
+            None => name.to_string(),
+        };
+        let fg = Color::Rgb {
+            r: col.r,
+            g: col.g,
+            b: col.b,
+        };
+        draw_text(buf, x + 1, top as u16, &label, fg, bg);
+    }
+
+    let span = match (samples.first(), samples.last()) {
+        (Some(first), Some(last)) if samples.len() >= 2 => format!(
+            "last {}",
+            format_secs((last.tick - first.tick) * tick_step_ms / 1000)
+        ),
+        _ => "not enough history yet".to_string(),
+    };
+    draw_text(buf, x + 1, 1, &span, Color::DarkGrey, bg);
+}
+
 /* -----------------------------
    UI overlay (text + meters)
 ------------------------------ */
@@ -515,17 +762,28 @@ fn bar(value01: f32, width: usize) -> String {
     s
 }
 
-pub(crate) fn ui_overlay(buf: &mut CellBuffer, st: &GameState, settings: &Settings) {
+pub(crate) fn ui_overlay(
+    buf: &mut CellBuffer,
+    st: &GameState,
+    settings: &Settings,
+    foods: &FoodCatalog,
+    settings_note: Option<&str>,
+    tick_step_ms: u64,
+) {
     let bg = Color::Black;
     let fg = Color::White;
 
-    let title = format!(
-        "Termigotchi  |  {} ({:?})  |  Mood: {:?}",
-        st.pet.name, st.pet.stage, st.pet.mood
+    let pet = st.pet();
+    let mut title = format!(
+        "Termigotchi  |  {} ({:?} {})  |  Mood: {:?}",
+        pet.name, pet.stage, pet.species_id, pet.mood
     );
+    if let Some(sky) = sky_label(st) {
+        title.push_str(&format!("  |  {sky}"));
+    }
     draw_text(buf, 1, 0, &title, fg, bg);
 
-    let m = st.pet.meters;
+    let m = pet.meters;
 
     let lines = [
         ("Hunger", m.hunger),
@@ -543,83 +801,389 @@ pub(crate) fn ui_overlay(buf: &mut CellBuffer, st: &GameState, settings: &Settin
 
     let flags = format!(
         "Flags: sleep={} sick={} dirty={} poop={} call={}",
-        st.pet.flags.sleeping as u8,
-        st.pet.flags.sick as u8,
-        st.pet.flags.dirty as u8,
-        st.pet.flags.has_poop as u8,
-        st.pet.flags.attention_call as u8
+        pet.flags.sleeping as u8,
+        pet.flags.sick as u8,
+        pet.flags.dirty as u8,
+        pet.flags.has_poop as u8,
+        pet.flags.attention_call as u8
     );
     draw_text(buf, 1, 8, &flags, fg, bg);
+    draw_household(buf, st, 1, 9);
 
     let help = match st.scene {
         Scene::Main => {
-            "Keys: q quit | f feed | p play | c clean | m medicine | s sleep | tab settings | h help"
+            "Keys: q quit | f feed | p play | c clean | m medicine | s sleep | j journal | g graveyard | l lights | a adopt | ←→ pet | tab settings | h help"
+        }
+        Scene::Settings => {
+            "Settings: ↑↓ select | ←→ change | enter apply | esc back | tab back | h help"
         }
-        Scene::Settings => "Settings: ↑↓ select | enter apply | esc back | tab back | h help",
         Scene::Help => "Help: esc back | h close | q quit",
         Scene::Rename => "Rename: type name | enter save | esc cancel",
+        Scene::SeedEdit => "Seed: type a number (0x for hex) | enter save | esc cancel",
+        Scene::Food => "Feed: ↑↓ select | enter feed | esc back | h help",
+        Scene::Game(_) => "Game: ←→ ↑↓ space play | enter finish | esc quit game",
         Scene::Recap(_) => "Recap: any key to continue",
-        Scene::Dead => "Dead: n new game | q quit",
+        Scene::History => "Journal: ↑↓ scroll | esc back | j close",
+        Scene::Dead => "Dead: n new egg | ←→ other pets | g graveyard | q quit",
+        Scene::Farewell => "Farewell: n new egg | ←→ other pets | g graveyard | q quit",
+        Scene::Graveyard => "Graveyard: ↑↓ select | esc back | g close",
     };
     draw_text(buf, 1, buf.h.saturating_sub(1), help, fg, bg);
 
     if matches!(st.scene, Scene::Settings) {
-        draw_settings(buf, st, settings);
+        draw_settings(buf, st, settings, settings_note);
+    }
+    if matches!(st.scene, Scene::Food) {
+        draw_food_menu(buf, st, foods);
+    }
+    if let Scene::Game(ref g) = st.scene {
+        draw_game(buf, st, g);
+    }
+    if matches!(st.scene, Scene::History) {
+        draw_journal(buf, st, tick_step_ms);
+    }
+    if matches!(st.scene, Scene::Graveyard) {
+        draw_graveyard(buf, st);
+    }
+}
+
+// One name per pet: the selected one bracketed, the departed greyed out.
+fn draw_household(buf: &mut CellBuffer, st: &GameState, x: u16, y: u16) {
+    let bg = Color::Black;
+    let label = format!("Pets {}/{}:", st.pets.len(), HOUSEHOLD_MAX);
+    draw_text(buf, x, y, &label, Color::White, bg);
+    let mut cx = x + label.chars().count() as u16 + 1;
+    for (i, pet) in st.pets.iter().enumerate() {
+        let text = if i == st.selected {
+            format!("[{}]", pet.name)
+        } else {
+            pet.name.clone()
+        };
+        let fg = if pet.flags.dead {
+            Color::DarkGrey
+        } else if i == st.selected {
+            Color::Yellow
+        } else if pet.flags.attention_call {
+            Color::Red
+        } else {
+            Color::White
+        };
+        draw_text(buf, cx, y, &text, fg, bg);
+        cx += text.chars().count() as u16 + 1;
+    }
+    if st.pets.len() >= HOUSEHOLD_MAX {
+        draw_text(buf, cx, y, "(full)", Color::DarkGrey, bg);
     }
 }
 
 /* -----------------------------
-   Settings UI
+   Life journal
 ------------------------------ */
 
-pub(crate) fn draw_settings(buf: &mut CellBuffer, st: &GameState, settings: &Settings) {
+pub(crate) fn draw_journal(buf: &mut CellBuffer, st: &GameState, tick_step_ms: u64) {
+    let bg = Color::Black;
+    let fg = Color::White;
+
+    let start_x = 1;
+    let start_y = 11;
+    // stay inside the left panel so the charts remain readable
+    let panel = (buf.w / 3).max(26).min(buf.w.saturating_sub(10));
+    let width = panel.saturating_sub(2) as usize;
+
+    let entries = &st.pet().journal.entries;
+    let title = format!("Journal ({} entries)", entries.len());
+    draw_text(buf, start_x, start_y, &title, fg, bg);
+
+    let visible = (buf.h as usize).saturating_sub(start_y as usize + 4).max(1);
+    for (row, entry) in entries
+        .iter()
+        .rev()
+        .skip(st.journal_scroll)
+        .take(visible)
+        .enumerate()
+    {
+        // entries from before timestamps were kept fall back to the pet's age
+        let when = match entry.at {
+            Some(at) => at.with_timezone(&Local).format("%m-%d %H:%M").to_string(),
+            None => format_secs(entry.tick * tick_step_ms / 1000),
+        };
+        let line: String = format!("{when:>11} {}", entry.event.describe())
+            .chars()
+            .take(width)
+            .collect();
+        draw_text(buf, start_x, start_y + 2 + row as u16, &line, fg, bg);
+    }
+}
+
+/* -----------------------------
+   Graveyard
+------------------------------ */
+
+// The departed, newest first, in the left panel; the selected one's headstone on the right.
+pub(crate) fn draw_graveyard(buf: &mut CellBuffer, st: &GameState) {
     let bg = Color::Black;
     let fg = Color::White;
     let hi = Color::Yellow;
 
     let start_x = 1;
     let start_y = 11;
+    let panel = (buf.w / 3).max(26).min(buf.w.saturating_sub(10));
+    let width = panel.saturating_sub(2) as usize;
+
+    let title = format!("Graveyard ({})", st.graveyard.len());
+    draw_text(buf, start_x, start_y, &title, fg, bg);
+    if st.graveyard.is_empty() {
+        draw_text(
+            buf,
+            start_x,
+            start_y + 2,
+            "No one has passed on yet.",
+            Color::DarkGrey,
+            bg,
+        );
+        return;
+    }
 
-    draw_text(buf, start_x, start_y, "Settings", fg, bg);
+    let visible = (buf.h as usize).saturating_sub(start_y as usize + 4).max(1);
+    let first = st.graveyard_cursor.saturating_sub(visible - 1);
+    for (row, (i, m)) in st
+        .graveyard
+        .iter()
+        .rev()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .enumerate()
+    {
+        let selected = i == st.graveyard_cursor;
+        let line: String = format!(
+            "{} {} ({:?}, gen {})",
+            if selected { ">" } else { " " },
+            m.name,
+            m.stage,
+            m.generation
+        )
+        .chars()
+        .take(width)
+        .collect();
+        let col = if selected { hi } else { fg };
+        draw_text(buf, start_x, start_y + 2 + row as u16, &line, col, bg);
+    }
 
-    let selected = st.settings_cursor;
-    let render_mode = if settings.enable_braille {
-        "Braille"
-    } else {
-        "ASCII"
+    let Some(m) = st.graveyard.iter().rev().nth(st.graveyard_cursor) else {
+        return;
     };
-    let line = format!(
-        "{} Render: {}",
-        if selected == 0 { ">" } else { " " },
-        render_mode
-    );
-    draw_text(
-        buf,
-        start_x,
-        start_y + 2,
-        &line,
-        if selected == 0 { hi } else { fg },
-        bg,
-    );
-
-    let mut name_display = st.pet.name.clone();
-    if name_display.len() > 16 {
-        name_display.truncate(15);
-        name_display.push_str("...");
+    let cause = match m.cause {
+        Some(DeathCause::OldAge) => ", died of old age",
+        Some(DeathCause::Illness) => ", died of illness",
+        None => "",
+    };
+    let h = &m.history;
+    let lines = [
+        format!("Here lies {}", m.name),
+        format!(
+            "{} ({:?}), generation {}",
+            m.species_id, m.stage, m.generation
+        ),
+        format!("Lived {}{cause}", format_secs(m.age_secs)),
+        m.died_at
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        String::new(),
+        format!(
+            "Meals: {}  Games: {}  Baths: {}",
+            h.feed_events, h.play_events, h.clean_events
+        ),
+        format!(
+            "Lessons: {}  Illnesses: {}  Ignored calls: {}",
+            h.discipline_events, h.sickness_events, h.neglected_events
+        ),
+    ];
+    for (row, line) in lines.iter().enumerate() {
+        draw_text(buf, panel + 2, start_y + row as u16, line, fg, bg);
     }
-    let name_line = format!(
-        "{} Name: {}",
-        if selected == 1 { ">" } else { " " },
-        name_display
+}
+
+/* -----------------------------
+   Mini-games
+------------------------------ */
+
+pub(crate) fn draw_game(buf: &mut CellBuffer, st: &GameState, g: &MiniGame) {
+    let bg = Color::Black;
+    let fg = Color::White;
+
+    let start_x = 1;
+    let start_y = 11;
+
+    draw_text(buf, start_x, start_y, g.kind.title(), fg, bg);
+
+    let round = format!(
+        "Round {}/{}  Wins: {}",
+        (g.round + 1).min(g.rounds),
+        g.rounds,
+        g.wins
     );
+    draw_text(buf, start_x, start_y + 2, &round, fg, bg);
+
+    let go = g.kind == MiniGameKind::Reaction && !g.finished && st.sim_ticks >= g.go_tick;
     draw_text(
         buf,
         start_x,
         start_y + 3,
-        &name_line,
-        if selected == 1 { hi } else { fg },
+        &g.prompt(st.sim_ticks),
+        if go { Color::Yellow } else { fg },
         bg,
     );
+
+    let (last, col) = match g.last {
+        Some(true) => ("Nice one!", Color::Green),
+        Some(false) => ("Missed...", Color::Red),
+        None => ("", fg),
+    };
+    draw_text(buf, start_x, start_y + 5, last, col, bg);
+}
+
+/* -----------------------------
+   Food picker
+------------------------------ */
+
+pub(crate) fn draw_food_menu(buf: &mut CellBuffer, st: &GameState, foods: &FoodCatalog) {
+    let bg = Color::Black;
+    let fg = Color::White;
+    let hi = Color::Yellow;
+
+    let start_x = 1;
+    let start_y = 11;
+
+    draw_text(buf, start_x, start_y, "Feed", fg, bg);
+
+    // Keep the cursor visible when the list is taller than the panel.
+    let visible = (buf.h as usize).saturating_sub(start_y as usize + 4).max(1);
+    let first = st.food_cursor.saturating_sub(visible - 1);
+
+    for (row, (i, item)) in foods
+        .foods
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .enumerate()
+    {
+        let selected = i == st.food_cursor;
+        let mark = match foods.taste(&st.pet().species_id, &item.id) {
+            Taste::Favorite => '♥',
+            Taste::Neutral => ' ',
+            Taste::Disliked => '✗',
+        };
+        let mut name = item.name.clone();
+        if name.chars().count() > 14 {
+            name = name.chars().take(13).collect();
+            name.push('…');
+        }
+        let line = format!(
+            "{} {:<14} {:<7} {}",
+            if selected { ">" } else { " " },
+            name,
+            item.kind.label(),
+            mark
+        );
+        draw_text(
+            buf,
+            start_x,
+            start_y + 2 + row as u16,
+            &line,
+            if selected { hi } else { fg },
+            bg,
+        );
+    }
+}
+
+/* -----------------------------
+   Settings UI
+------------------------------ */
+
+pub(crate) fn draw_settings(
+    buf: &mut CellBuffer,
+    st: &GameState,
+    settings: &Settings,
+    note: Option<&str>,
+) {
+    let bg = Color::Black;
+    let fg = Color::White;
+    let hi = Color::Yellow;
+
+    let start_x = 1;
+    let start_y = 11;
+
+    draw_text(buf, start_x, start_y, "Settings", fg, bg);
+
+    // Keep the cursor visible when the list is taller than the panel; the row under the
+    // list is left for the note.
+    let visible = (buf.h as usize).saturating_sub(start_y as usize + 4).max(1);
+    let first = st.settings_cursor.saturating_sub(visible - 1);
+
+    let on_off = |b: bool| if b { "on" } else { "off" };
+    for (row, (i, item)) in SettingsItem::ALL
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .enumerate()
+    {
+        let value = match item {
+            SettingsItem::FpsCap => format!("FPS cap: {}", settings.fps_cap),
+            SettingsItem::Color => format!("Color: {}", on_off(settings.enable_color)),
+            SettingsItem::Braille => format!(
+                "Render: {}",
+                if settings.enable_braille {
+                    "Braille"
+                } else {
+                    "ASCII"
+                }
+            ),
+            SettingsItem::Skin => format!("Skin: {}", settings.skin_name),
+            SettingsItem::Seed => format!("Seed: {} (next egg)", settings.seed),
+            SettingsItem::Name => {
+                let mut name_display = st.pet().name.clone();
+                if name_display.len() > 16 {
+                    name_display.truncate(15);
+                    name_display.push_str("...");
+                }
+                format!("Name: {name_display}")
+            }
+            SettingsItem::RulesPreset => {
+                format!("Rules: {} (next egg)", settings.rules_preset)
+            }
+            SettingsItem::Inheritance => {
+                format!("Inheritance: {} (next egg)", on_off(settings.inheritance))
+            }
+            SettingsItem::Schedule => format!("Bedtime schedule: {}", on_off(settings.schedule)),
+            SettingsItem::Bedtime => format!("Bedtime: {:02}:00", settings.bedtime_hour),
+            SettingsItem::WakeTime => format!("Wake up: {:02}:00", settings.wake_hour),
+            SettingsItem::Hooks => format!(
+                "Hooks: {} ({} set in settings.json)",
+                on_off(settings.hooks.enabled),
+                settings.hooks.configured()
+            ),
+            SettingsItem::Reset => "Reset to defaults".to_string(),
+        };
+        let selected = i == st.settings_cursor;
+        let line = format!("{} {}", if selected { ">" } else { " " }, value);
+        draw_text(
+            buf,
+            start_x,
+            start_y + 2 + row as u16,
+            &line,
+            if selected { hi } else { fg },
+            bg,
+        );
+    }
+
+    if let Some(note) = note {
+        // just above the help line
+        draw_text(buf, start_x, buf.h.saturating_sub(2), note, Color::Red, bg);
+    }
 }
 
 pub(crate) fn draw_pet_ascii(buf: &mut CellBuffer, st: &GameState, cx: i32, cy: i32) {
@@ -643,9 +1207,9 @@ pub(crate) fn draw_pet_ascii(buf: &mut CellBuffer, st: &GameState, cx: i32, cy:
     ];
 
     // mood tweak: mouth shape
-    if matches!(st.pet.mood, Mood::Sad | Mood::Sick | Mood::Angry) {
+    if matches!(st.pet().mood, Mood::Sad | Mood::Sick | Mood::Angry) {
         grid[4] = "   |   /___\\   | ";
-    } else if matches!(st.pet.mood, Mood::Happy) {
+    } else if matches!(st.pet().mood, Mood::Happy) {
         grid[4] = "   |   \\___/   | ";
     }
 
@@ -654,8 +1218,8 @@ pub(crate) fn draw_pet_ascii(buf: &mut CellBuffer, st: &GameState, cx: i32, cy:
         if y < 0 || y >= buf.h as i32 {
             continue;
         }
-        let mut x = x0;
-        for ch in line.chars() {
+        for (xx, ch) in line.chars().enumerate() {
+            let x = x0 + xx as i32;
             if x >= 0 && x < buf.w as i32 {
                 buf.set(
                     x as u16,
@@ -668,11 +1232,10 @@ pub(crate) fn draw_pet_ascii(buf: &mut CellBuffer, st: &GameState, cx: i32, cy:
                     },
                 );
             }
-            x += 1;
         }
     }
 
-    if st.pet.flags.attention_call {
+    if st.pet().flags.attention_call {
         let ax = cx;
         let ay = y0 - 2;
         if ay >= 0 && ay < buf.h as i32 && ax >= 0 && ax < buf.w as i32 {
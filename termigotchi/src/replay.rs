@@ -0,0 +1,218 @@
+use crate::food::FoodCatalog;
+use crate::model::{GameState, Rules};
+use crate::sim::PlayerAction;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+// One JSON object per line: a Start record, then Action records (and Rules records when a
+// new egg switches presets), then End when the session closed cleanly. `step` counts fixed
+// sim steps since the recording began, so a dead pet (whose sim_ticks stop) or a new egg
+// (whose sim_ticks restart) replays the same.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "record", rename_all = "snake_case")]
+enum Record {
+    Start {
+        rules: Rules,
+        foods: FoodCatalog,
+        state: Box<GameState>,
+    },
+    Action {
+        step: u64,
+        action: PlayerAction,
+    },
+    Rules {
+        step: u64,
+        rules: Rules,
+    },
+    End {
+        step: u64,
+    },
+}
+
+pub(crate) struct Recorder {
+    out: BufWriter<File>,
+    steps: u64,
+}
+
+impl Recorder {
+    pub(crate) fn create(
+        path: &Path,
+        rules: &Rules,
+        foods: &FoodCatalog,
+        state: &GameState,
+    ) -> Result<Self> {
+        let file =
+            File::create(path).with_context(|| format!("creating recording {}", path.display()))?;
+        let mut rec = Self {
+            out: BufWriter::new(file),
+            steps: 0,
+        };
+        rec.write(&Record::Start {
+            rules: rules.clone(),
+            foods: foods.clone(),
+            state: Box::new(state.clone()),
+        })?;
+        Ok(rec)
+    }
+
+    fn write(&mut self, record: &Record) -> Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        self.out.write_all(b"\n")?;
+        // keep the log usable if the game is killed
+        self.out.flush()?;
+        Ok(())
+    }
+
+    pub(crate) fn step(&mut self) {
+        self.steps += 1;
+    }
+
+    pub(crate) fn action(&mut self, action: &PlayerAction) -> Result<()> {
+        self.write(&Record::Action {
+            step: self.steps,
+            action: action.clone(),
+        })
+    }
+
+    pub(crate) fn rules(&mut self, rules: &Rules) -> Result<()> {
+        self.write(&Record::Rules {
+            step: self.steps,
+            rules: rules.clone(),
+        })
+    }
+
+    pub(crate) fn finish(mut self) -> Result<()> {
+        self.write(&Record::End { step: self.steps })
+    }
+}
+
+pub(crate) struct Replayed {
+    pub(crate) state: GameState,
+    pub(crate) rules: Rules,
+    pub(crate) steps: u64,
+    // false when the recording stops without an End record
+    pub(crate) complete: bool,
+}
+
+// Rebuild the final state of a recorded session. Without an End record (the game was
+// killed) the replay stops at the last recorded action.
+pub(crate) fn replay(path: &Path) -> Result<Replayed> {
+    let file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let first = lines.next().context("recording is empty")??;
+    let Record::Start {
+        mut rules,
+        foods,
+        state,
+    } = serde_json::from_str(&first).context("reading the start record")?
+    else {
+        bail!("recording does not begin with a start record");
+    };
+    let mut state = *state;
+
+    let mut steps = 0;
+    let mut complete = false;
+    for (n, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Record =
+            serde_json::from_str(&line).with_context(|| format!("line {}", n + 2))?;
+        let target = match &record {
+            Record::Start { .. } => bail!("line {}: unexpected second start record", n + 2),
+            Record::Action { step, .. } | Record::Rules { step, .. } | Record::End { step } => {
+                *step
+            }
+        };
+        if target < steps {
+            bail!("line {}: step {target} goes backwards", n + 2);
+        }
+        while steps < target {
+            state.tick_live(&rules);
+            steps += 1;
+        }
+        match record {
+            Record::Action { action, .. } => state.apply(action, &foods),
+            Record::Rules { rules: next, .. } => rules = next,
+            _ => {
+                complete = true;
+                break;
+            }
+        }
+    }
+
+    Ok(Replayed {
+        state,
+        rules,
+        steps,
+        complete,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::GameKey;
+
+    #[test]
+    fn replay_rebuilds_the_recorded_run() {
+        let rules = Rules::default();
+        let foods = FoodCatalog::default();
+        let mut live = GameState::new(42);
+        let path = std::env::temp_dir().join(format!("tg-replay-{}.jsonl", std::process::id()));
+
+        let mut rec = Recorder::create(&path, &rules, &foods, &live).unwrap();
+        let script = [
+            (10, PlayerAction::AdoptEgg { seed: 77 }),
+            (11, PlayerAction::PetSelect(1)),
+            (30, PlayerAction::Feed("apple".to_string())),
+            (200, PlayerAction::PlayAny),
+            (201, PlayerAction::GameInput(GameKey::Left)),
+            (202, PlayerAction::GameInput(GameKey::Up)),
+            (205, PlayerAction::GameInput(GameKey::Press)),
+            (900, PlayerAction::Clean),
+            (1500, PlayerAction::SleepToggle),
+            (2400, PlayerAction::SleepToggle),
+            (2401, PlayerAction::DebugKill),
+            (
+                2500,
+                PlayerAction::Hatch {
+                    seed: 9,
+                    rules_preset: "classic".to_string(),
+                    inherit: true,
+                },
+            ),
+        ];
+        let mut steps = 0;
+        for (at, action) in script {
+            while steps < at {
+                live.tick_live(&rules);
+                rec.step();
+                steps += 1;
+            }
+            rec.action(&action).unwrap();
+            live.apply(action, &foods);
+        }
+        for _ in 0..300 {
+            live.tick_live(&rules);
+            rec.step();
+        }
+        rec.finish().unwrap();
+
+        let replayed = replay(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(replayed.complete);
+        assert_eq!(replayed.steps, 2800);
+        assert_eq!(
+            serde_json::to_value(&replayed.state).unwrap(),
+            serde_json::to_value(&live).unwrap()
+        );
+    }
+}
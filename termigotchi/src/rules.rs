@@ -0,0 +1,100 @@
+use crate::config::write_json_atomic;
+use crate::model::{DecayRates, LifeStage, Rules};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+pub(crate) const DEFAULT_PRESET: &str = "classic";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct RulesBook {
+    pub(crate) presets: BTreeMap<String, Rules>,
+}
+
+impl RulesBook {
+    pub(crate) fn get(&self, name: &str) -> Option<&Rules> {
+        self.presets.get(name)
+    }
+
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.presets.keys().cloned().collect()
+    }
+}
+
+fn scaled(base: &Rules, meter_rate_scale: f32, decay_mul: f32, catchup_days: i64) -> Rules {
+    let mut r = base.clone();
+    r.meter_rate_scale = meter_rate_scale;
+    r.catchup_max_secs = catchup_days * 24 * 3600;
+    for d in r.decay_by_stage.values_mut() {
+        *d = DecayRates {
+            hunger: d.hunger * decay_mul,
+            happiness: d.happiness * decay_mul,
+            energy: d.energy * decay_mul,
+            hygiene: d.hygiene * decay_mul,
+        };
+    }
+    r
+}
+
+pub(crate) fn builtin_presets() -> RulesBook {
+    let classic = Rules::default();
+    let mut presets = BTreeMap::new();
+    presets.insert("relaxed".to_string(), scaled(&classic, 0.01, 0.8, 3));
+    presets.insert("hardcore".to_string(), scaled(&classic, 0.04, 1.25, 14));
+    presets.insert(DEFAULT_PRESET.to_string(), classic);
+    RulesBook { presets }
+}
+
+fn validate(name: &str, r: &Rules) -> Result<()> {
+    let ctx = |msg: String| format!("preset '{name}': {msg}");
+    if r.tick_step_ms == 0 || r.tick_step_ms > 60_000 {
+        bail!(ctx(format!("tick_step_ms {} must be 1..=60000", r.tick_step_ms)));
+    }
+    if r.catchup_step_ms < r.tick_step_ms {
+        bail!(ctx("catchup_step_ms must be at least tick_step_ms".to_string()));
+    }
+    if r.catchup_max_secs < 0 {
+        bail!(ctx("catchup_max_secs must not be negative".to_string()));
+    }
+    if !r.meter_rate_scale.is_finite() || r.meter_rate_scale <= 0.0 {
+        bail!(ctx("meter_rate_scale must be a positive number".to_string()));
+    }
+    if !r.companionship.is_finite() || r.companionship < 0.0 {
+        bail!(ctx("companionship must be >= 0".to_string()));
+    }
+    if !(0.0..=1.0).contains(&r.night_metabolism) {
+        bail!(ctx("night_metabolism must be between 0 and 1".to_string()));
+    }
+    for stage in LifeStage::ALL {
+        let Some(d) = r.decay_by_stage.get(&stage) else {
+            bail!(ctx(format!("decay_by_stage is missing {stage:?}")));
+        };
+        for v in [d.hunger, d.happiness, d.energy, d.hygiene] {
+            if !v.is_finite() || v < 0.0 {
+                bail!(ctx(format!("decay rates for {stage:?} must be >= 0")));
+            }
+        }
+    }
+    Ok(())
+}
+
+// Built-in presets, overridden or extended by `rules.json`. A missing file is written out
+// with the built-ins so it can be tuned; an unreadable or invalid one is an error.
+pub(crate) fn load_rules(path: &Path) -> Result<RulesBook> {
+    let mut book = builtin_presets();
+    match fs::read_to_string(path) {
+        Ok(s) => {
+            let file: RulesBook =
+                serde_json::from_str(&s).with_context(|| format!("parsing {}", path.display()))?;
+            for (name, rules) in &file.presets {
+                validate(name, rules).with_context(|| format!("in {}", path.display()))?;
+            }
+            book.presets.extend(file.presets);
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let _ = write_json_atomic(path, &book);
+        }
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    }
+    Ok(book)
+}
@@ -1,81 +1,161 @@
+use crate::config::SettingsItem;
+use crate::food::{FoodCatalog, FoodItem, Taste};
+use crate::games::{GameKey, MiniGame, MiniGameKind};
+use crate::hooks::hook_kind;
+use crate::journal::{JournalEntry, JournalEvent};
 use crate::model::{
-    CatchupSummary, DecayRates, Flags, GameState, LifeStage, Meters, Mood, Rules, Scene,
+    CatchupSummary, DeathCause, DecayRates, Flags, GameState, History, LifeStage, Memorial, Meters,
+    Mood, Pet, Rules, Scene, Schedule, HOUSEHOLD_MAX,
 };
 use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub(crate) enum PlayerAction {
-    Feed(&'static str),
+    Feed(String),
+    FoodMenuOpen,
+    FoodMove(i32),
+    FoodPick,
     PlayAny,
+    Play(MiniGameKind),
+    GameInput(GameKey),
     Clean,
     Medicine,
     SleepToggle,
     Discipline,
+    LightsToggle,
     DebugKill,
     HelpToggle,
+    HistoryToggle,
+    HistoryScroll(i32),
+    GraveyardToggle,
+    GraveyardMove(i32),
     RenameOpen,
     RenameChar(char),
     RenameBackspace,
     RenameCommit,
     RenameCancel,
     SettingsMove(i32),
+    SettingsAdjust(i32),
     SettingsToggle,
     SettingsOpen,
+    SeedEditOpen(String),
+    SeedEditChar(char),
+    SeedEditBackspace,
+    SeedEditCommit,
+    SeedEditClose,
     Back,
     Quit,
+    PetSelect(i32),
     NewGame,
+    Hatch {
+        seed: u64,
+        rules_preset: String,
+        // the egg takes after the dead pet it replaces
+        #[serde(default)]
+        inherit: bool,
+    },
+    Adopt,
+    AdoptEgg {
+        seed: u64,
+    },
+    SetSchedule(Option<Schedule>),
 }
 
+// What the household clock means for one pet tick.
+#[derive(Clone, Copy, Debug, Default)]
+struct Bedtime {
+    night: bool,
+    // far enough past bedtime that being awake is the player's doing
+    late: bool,
+    // the night ended this tick
+    morning: bool,
+    lights_off: bool,
+}
+
+// A pet left alone nods off well within this many minutes after bedtime.
+const BEDTIME_GRACE_MINS: i64 = 30;
+
 impl GameState {
-    pub(crate) fn apply(&mut self, action: PlayerAction) {
+    pub(crate) fn apply(&mut self, action: PlayerAction, foods: &FoodCatalog) {
         match action {
             PlayerAction::Feed(id) => {
-                let _ = id;
-                self.pet.meters.hunger = (self.pet.meters.hunger + 18.0).clamp(0.0, 100.0);
-                self.pet.meters.happiness = (self.pet.meters.happiness + 3.0).clamp(0.0, 100.0);
-                self.pet.meters.weight = (self.pet.meters.weight + 0.6).clamp(0.0, 200.0);
-                self.pet.history.feed_events += 1;
-                self.last_action_at_tick = self.sim_ticks;
-
-                // deterministic delayed poop chance: just roll now for skeleton
-                if self.rng.roll(0.25) {
-                    self.pet.flags.has_poop = true;
+                if let Some(item) = foods.get(&id) {
+                    let now = self.clock;
+                    let pet = self.pet_mut();
+                    let taste = foods.taste(&pet.species_id, &item.id);
+                    pet.feed(item, taste, now);
+                }
+            }
+            PlayerAction::FoodMenuOpen => {
+                self.scene = Scene::Food;
+                self.food_cursor = self.food_cursor.min(foods.foods.len().saturating_sub(1));
+            }
+            PlayerAction::FoodMove(delta) => {
+                let len = foods.foods.len().max(1) as i32;
+                self.food_cursor = (self.food_cursor as i32 + delta).rem_euclid(len) as usize;
+            }
+            PlayerAction::FoodPick => {
+                self.scene = Scene::Main;
+                if let Some(item) = foods.foods.get(self.food_cursor) {
+                    self.apply(PlayerAction::Feed(item.id.clone()), foods);
                 }
             }
             PlayerAction::PlayAny => {
-                self.pet.meters.happiness = (self.pet.meters.happiness + 10.0).clamp(0.0, 100.0);
-                self.pet.meters.energy = (self.pet.meters.energy - 7.0).clamp(0.0, 100.0);
-                self.pet.meters.hunger = (self.pet.meters.hunger - 1.5).clamp(0.0, 100.0);
-                self.pet.history.play_events += 1;
-                self.last_action_at_tick = self.sim_ticks;
+                let pick =
+                    (self.pet_mut().rng.next_u64() % MiniGameKind::ALL.len() as u64) as usize;
+                self.apply(PlayerAction::Play(MiniGameKind::ALL[pick]), foods);
+            }
+            PlayerAction::Play(kind) => {
+                let now = self.sim_ticks;
+                let pet = &mut self.pets[self.selected];
+                pet.touch();
+                self.scene = Scene::Game(MiniGame::start(kind, &mut pet.rng, now));
+            }
+            PlayerAction::GameInput(key) => {
+                let (now, clock) = (self.sim_ticks, self.clock);
+                let pet = &mut self.pets[self.selected];
+                let mut leave = false;
+                if let Scene::Game(g) = &mut self.scene {
+                    if g.finished {
+                        leave = key == GameKey::Press;
+                    } else if g.input(key, &mut pet.rng, now) {
+                        pet.finish_game(g, clock);
+                    }
+                }
+                if leave {
+                    self.scene = Scene::Main;
+                }
+                pet.touch();
             }
             PlayerAction::Clean => {
-                self.pet.meters.hygiene = 100.0;
-                self.pet.flags.dirty = false;
-                self.pet.flags.has_poop = false;
-                self.pet.history.clean_events += 1;
-                self.last_action_at_tick = self.sim_ticks;
+                let now = self.clock;
+                self.pet_mut().clean(now);
             }
             PlayerAction::Medicine => {
-                if self.pet.flags.sick {
-                    self.pet.flags.sick = false;
-                    self.pet.meters.health = (self.pet.meters.health + 22.0).clamp(0.0, 100.0);
-                    self.last_action_at_tick = self.sim_ticks;
-                }
+                let now = self.clock;
+                self.pet_mut().medicine(now);
             }
             PlayerAction::SleepToggle => {
-                self.pet.flags.sleeping = !self.pet.flags.sleeping;
-                self.last_action_at_tick = self.sim_ticks;
+                let now = self.clock;
+                self.pet_mut().toggle_sleep(now);
             }
             PlayerAction::Discipline => {
-                self.pet.meters.discipline =
-                    (self.pet.meters.discipline + 12.0).clamp(0.0, 100.0);
-                self.pet.flags.attention_call = false;
-                self.pet.history.discipline_events += 1;
-                self.last_action_at_tick = self.sim_ticks;
+                let now = self.clock;
+                self.pet_mut().discipline(now);
             }
+            PlayerAction::LightsToggle => self.lights_off = !self.lights_off,
             PlayerAction::DebugKill => {
-                self.pet.flags.dead = true;
+                let now = self.clock;
+                let pet = self.pet_mut();
+                pet.flags.dead = true;
+                pet.cause_of_death = Some(DeathCause::Illness);
+                pet.log(
+                    now,
+                    JournalEvent::Died {
+                        cause: DeathCause::Illness,
+                    },
+                );
                 self.scene = Scene::Dead;
             }
             PlayerAction::HelpToggle => {
@@ -84,8 +164,32 @@ impl GameState {
                     _ => Scene::Help,
                 };
             }
+            PlayerAction::HistoryToggle => {
+                self.scene = match self.scene {
+                    Scene::History => Scene::Main,
+                    _ => Scene::History,
+                };
+                self.journal_scroll = 0;
+            }
+            PlayerAction::HistoryScroll(delta) => {
+                let last = self.pet().journal.entries.len().saturating_sub(1) as i64;
+                self.journal_scroll =
+                    (self.journal_scroll as i64 + delta as i64).clamp(0, last) as usize;
+            }
+            PlayerAction::GraveyardToggle => {
+                self.scene = match self.scene {
+                    Scene::Graveyard => Scene::Main,
+                    _ => Scene::Graveyard,
+                };
+                self.graveyard_cursor = 0;
+            }
+            PlayerAction::GraveyardMove(delta) => {
+                let last = self.graveyard.len().saturating_sub(1) as i64;
+                self.graveyard_cursor =
+                    (self.graveyard_cursor as i64 + delta as i64).clamp(0, last) as usize;
+            }
             PlayerAction::RenameOpen => {
-                self.name_edit = self.pet.name.clone();
+                self.name_edit = self.pet().name.clone();
                 self.scene = Scene::Rename;
             }
             PlayerAction::RenameChar(ch) => {
@@ -98,9 +202,17 @@ impl GameState {
                 self.name_edit.pop();
             }
             PlayerAction::RenameCommit => {
-                let trimmed = self.name_edit.trim();
-                if !trimmed.is_empty() {
-                    self.pet.name = trimmed.to_string();
+                let trimmed = self.name_edit.trim().to_string();
+                let now = self.clock;
+                let pet = self.pet_mut();
+                if !trimmed.is_empty() && trimmed != pet.name {
+                    pet.name = trimmed;
+                    pet.log(
+                        now,
+                        JournalEvent::Renamed {
+                            name: pet.name.clone(),
+                        },
+                    );
                 }
                 self.scene = Scene::Settings;
             }
@@ -108,7 +220,7 @@ impl GameState {
                 self.scene = Scene::Settings;
             }
             PlayerAction::SettingsMove(delta) => {
-                let len = 2i32;
+                let len = SettingsItem::ALL.len() as i32;
                 let mut next = self.settings_cursor as i32 + delta;
                 if next < 0 {
                     next = len - 1;
@@ -117,138 +229,575 @@ impl GameState {
                 }
                 self.settings_cursor = next as usize;
             }
-            PlayerAction::SettingsToggle => {}
+            // these touch Settings, so App handles them
+            PlayerAction::SettingsAdjust(_)
+            | PlayerAction::SettingsToggle
+            | PlayerAction::SeedEditCommit => {}
             PlayerAction::SettingsOpen => {
                 self.scene = Scene::Settings;
                 self.settings_cursor = 0;
             }
+            PlayerAction::SeedEditOpen(current) => {
+                self.seed_edit = current;
+                self.scene = Scene::SeedEdit;
+            }
+            PlayerAction::SeedEditChar(ch) => {
+                // u64::MAX in hex with its 0x prefix
+                const SEED_MAX_LEN: usize = 20;
+                if self.seed_edit.len() < SEED_MAX_LEN {
+                    self.seed_edit.push(ch);
+                }
+            }
+            PlayerAction::SeedEditBackspace => {
+                self.seed_edit.pop();
+            }
+            PlayerAction::SeedEditClose => {
+                self.seed_edit.clear();
+                self.scene = Scene::Settings;
+            }
             PlayerAction::Back => self.scene = Scene::Main,
             PlayerAction::Quit => {}
-            PlayerAction::NewGame => {
-                let seed = self.rng.seed;
-                *self = GameState::new(seed);
+            PlayerAction::PetSelect(delta) => {
+                let len = self.pets.len() as i32;
+                self.selected = (self.selected as i32 + delta).rem_euclid(len) as usize;
+                self.journal_scroll = 0;
+                self.scene = if self.pet().flags.dead {
+                    self.death_scene()
+                } else {
+                    Scene::Main
+                };
+            }
+            // App decides the seed and preset and turns these into Hatch / AdoptEgg
+            PlayerAction::NewGame | PlayerAction::Adopt => {}
+            // The egg takes the selected pet's place; once nobody is left the household
+            // starts over under the given preset.
+            PlayerAction::Hatch {
+                seed,
+                rules_preset,
+                inherit,
+            } => {
+                let parent = (inherit && self.pet().flags.dead).then(|| self.pet().clone());
+                if self.all_dead() {
+                    let (clock, schedule) = (self.clock, self.schedule);
+                    let hook_log = std::mem::take(&mut self.hook_log);
+                    let graveyard = std::mem::take(&mut self.graveyard);
+                    *self = GameState::new(seed);
+                    self.rules_preset = rules_preset;
+                    self.clock = clock;
+                    self.schedule = schedule;
+                    self.hook_log = hook_log;
+                    self.graveyard = graveyard;
+                } else {
+                    *self.pet_mut() = Pet::new(seed);
+                    self.hatched += 1;
+                    self.scene = Scene::Main;
+                }
+                if let Some(parent) = parent {
+                    self.pet_mut().inherit_from(&parent);
+                }
+            }
+            PlayerAction::AdoptEgg { seed } => {
+                if self.pets.len() < HOUSEHOLD_MAX {
+                    self.pets.push(Pet::new(seed));
+                    self.hatched += 1;
+                    self.selected = self.pets.len() - 1;
+                    self.scene = Scene::Main;
+                }
+            }
+            PlayerAction::SetSchedule(schedule) => self.set_schedule(schedule),
+        }
+    }
+
+    pub(crate) fn set_schedule(&mut self, schedule: Option<Schedule>) {
+        self.schedule = schedule;
+        if schedule.is_none() {
+            self.lights_off = false;
+            for pet in &mut self.pets {
+                pet.flags.kept_up = false;
             }
         }
     }
 
+    // One step of the interactive loop: the sim tick plus switching to the death scene.
+    pub(crate) fn tick_live(&mut self, rules: &Rules) {
+        self.tick_fixed_step(rules);
+        if self.pet().flags.dead && !matches!(self.scene, Scene::Dead | Scene::Farewell) {
+            self.scene = self.death_scene();
+        }
+    }
+
     pub(crate) fn tick_fixed_step(&mut self, rules: &Rules) {
-        if self.pet.flags.dead {
+        // a pet can also die outside the sim (DebugKill), or in a save from before memorials
+        self.bury_the_dead(rules);
+        if self.all_dead() {
             return;
         }
 
         self.sim_ticks += 1;
-        self.pet.age_ticks += 1;
+        let was_night = self.is_night();
+        self.clock += ChronoDuration::milliseconds(rules.tick_step_ms as i64);
+        let night_mins = self.schedule.and_then(|s| s.night_minutes(self.clock));
+        let night = night_mins.is_some();
+        if was_night && !night {
+            self.lights_off = false;
+        }
+        let bedtime = Bedtime {
+            night,
+            late: night_mins.is_some_and(|m| m >= BEDTIME_GRACE_MINS),
+            morning: was_night && !night,
+            lights_off: self.lights_off,
+        };
+
+        let now = self.sim_ticks;
+        if let Scene::Game(g) = &mut self.scene {
+            let pet = &mut self.pets[self.selected];
+            if g.tick(&mut pet.rng, now) {
+                pet.finish_game(g, self.clock);
+            }
+        }
+
+        // moods as they were before this step, so the order pets tick in does not matter
+        let moods: Vec<Option<Mood>> = self
+            .pets
+            .iter()
+            .map(|p| (!p.flags.dead).then_some(p.mood))
+            .collect();
+        for (i, pet) in self.pets.iter_mut().enumerate() {
+            if pet.flags.dead {
+                continue;
+            }
+            let company = moods
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, mood)| match mood {
+                    Some(Mood::Happy) => 1.0,
+                    Some(Mood::Angry | Mood::Sick | Mood::Sad) => -1.0,
+                    _ => 0.0,
+                })
+                .sum::<f32>();
+            pet.tick(rules, company * rules.companionship, bedtime, self.clock);
+        }
+        self.bury_the_dead(rules);
+    }
+
+    // Give every pet that has died a memorial, once.
+    fn bury_the_dead(&mut self, rules: &Rules) {
+        for pet in &mut self.pets {
+            if !pet.flags.dead || pet.remembered {
+                continue;
+            }
+            pet.remembered = true;
+            self.graveyard.push(Memorial {
+                name: pet.name.clone(),
+                species_id: pet.species_id.clone(),
+                stage: pet.stage,
+                generation: pet.generation,
+                age_secs: pet.age_ticks * rules.tick_step_ms / 1000,
+                cause: pet.cause_of_death,
+                history: pet.history.clone(),
+                died_at: self.clock,
+            });
+        }
+    }
+}
+
+impl Pet {
+    // An egg takes after the pet whose place it takes: its constitution drifts a little
+    // from the parent's, hardier if the parent was well looked after, frailer if neglected.
+    fn inherit_from(&mut self, parent: &Pet) {
+        self.generation = parent.generation.saturating_add(1);
+        let h = &parent.history;
+        let care = h.feed_events + h.play_events + h.clean_events + h.discipline_events;
+        let neglect = h.neglected_events + h.sickness_events;
+        let nurture = if care >= 4 * neglect.max(1) {
+            0.97
+        } else if neglect > care {
+            1.03
+        } else {
+            1.0
+        };
+        let rng = &mut self.rng;
+        let mut drift =
+            |rate: f32| (rate * nurture * (0.95 + 0.1 * rng.next_f32())).clamp(0.75, 1.25);
+        let c = parent.constitution;
+        self.constitution = DecayRates {
+            hunger: drift(c.hunger),
+            happiness: drift(c.happiness),
+            energy: drift(c.energy),
+            hygiene: drift(c.hygiene),
+        };
+    }
+
+    pub(crate) fn log(&mut self, at: DateTime<Utc>, event: JournalEvent) {
+        if hook_kind(&event).is_some() {
+            self.outbox.push(JournalEntry {
+                tick: self.age_ticks,
+                at: Some(at),
+                event: event.clone(),
+            });
+        }
+        self.journal.log(self.age_ticks, at, event);
+    }
+
+    // Care timestamps run on the pet's own age, so housemates hatched later line up too.
+    fn touch(&mut self) {
+        self.last_action_at_tick = self.age_ticks;
+    }
+
+    fn feed(&mut self, item: &FoodItem, taste: Taste, now: DateTime<Utc>) {
+        // Favorites are a joy to eat; disliked food gets picked at and sulked over.
+        let (hunger, happiness, bond) = match taste {
+            Taste::Favorite => (item.hunger, item.happiness.max(0.0) * 1.5 + 4.0, 1.5),
+            Taste::Neutral => (item.hunger, item.happiness, 0.0),
+            Taste::Disliked => (item.hunger * 0.6, item.happiness.min(0.0) - 8.0, -0.5),
+        };
+
+        let m = &mut self.meters;
+        m.hunger = (m.hunger + hunger).clamp(0.0, 100.0);
+        m.happiness = (m.happiness + happiness).clamp(0.0, 100.0);
+        m.health = (m.health + item.health).clamp(0.0, 100.0);
+        m.energy = (m.energy + item.energy).clamp(0.0, 100.0);
+        m.bond = (m.bond + bond).clamp(0.0, 100.0);
+        m.weight = (m.weight + item.weight).clamp(0.0, 200.0);
+        self.flags.attention_call = false;
+        self.history.feed_events += 1;
+        self.touch();
+        self.log(
+            now,
+            JournalEvent::Fed {
+                food: item.name.clone(),
+            },
+        );
+
+        if self.rng.roll(item.poop_chance) {
+            self.flags.has_poop = true;
+        }
+    }
+
+    fn clean(&mut self, now: DateTime<Utc>) {
+        self.meters.hygiene = 100.0;
+        self.flags.dirty = false;
+        self.flags.has_poop = false;
+        self.history.clean_events += 1;
+        self.touch();
+        self.log(now, JournalEvent::Cleaned);
+    }
+
+    fn medicine(&mut self, now: DateTime<Utc>) {
+        if self.flags.sick {
+            self.flags.sick = false;
+            self.meters.health = (self.meters.health + 22.0).clamp(0.0, 100.0);
+            self.touch();
+            self.log(now, JournalEvent::Cured);
+        }
+    }
+
+    fn toggle_sleep(&mut self, now: DateTime<Utc>) {
+        self.flags.sleeping = !self.flags.sleeping;
+        self.touch();
+        self.log(
+            now,
+            if self.flags.sleeping {
+                JournalEvent::Slept
+            } else {
+                JournalEvent::Woke
+            },
+        );
+    }
+
+    fn discipline(&mut self, now: DateTime<Utc>) {
+        self.meters.discipline = (self.meters.discipline + 12.0).clamp(0.0, 100.0);
+        self.flags.attention_call = false;
+        self.history.discipline_events += 1;
+        self.touch();
+        self.log(now, JournalEvent::Disciplined);
+    }
+
+    // Rewards scale with how well the round went (score in 0..=1).
+    fn finish_game(&mut self, game: &MiniGame, now: DateTime<Utc>) {
+        let score = game.score();
+        let m = &mut self.meters;
+        m.happiness = (m.happiness + 4.0 + 12.0 * score).clamp(0.0, 100.0);
+        m.bond = (m.bond + 1.0 + 4.0 * score).clamp(0.0, 100.0);
+        m.weight = (m.weight - 0.2 - 0.6 * score).clamp(0.0, 200.0);
+        m.energy = (m.energy - 7.0).clamp(0.0, 100.0);
+        m.hunger = (m.hunger - 1.5).clamp(0.0, 100.0);
+        self.flags.attention_call = false;
+        self.history.play_events += 1;
+        self.log(
+            now,
+            JournalEvent::Played {
+                game: game.kind.title().to_string(),
+                wins: game.wins,
+                rounds: game.rounds,
+            },
+        );
+    }
+
+    // `company` is happiness/sec from housemates' moods, already scaled by the rules.
+    fn tick(&mut self, rules: &Rules, company: f32, bedtime: Bedtime, now: DateTime<Utc>) {
+        self.age_ticks += 1;
+
+        // the schedule: up at dawn, dozing off after bedtime (at once in the dark)
+        if bedtime.morning {
+            self.flags.kept_up = false;
+            if self.flags.sleeping {
+                self.flags.sleeping = false;
+                self.log(now, JournalEvent::Woke);
+            }
+        }
+        if bedtime.night
+            && !self.flags.sleeping
+            && (bedtime.lights_off || self.rng.roll(rules.tick_step_ms as f32 / 120_000.0))
+        {
+            self.flags.sleeping = true;
+            self.log(now, JournalEvent::Slept);
+        }
 
         let dt = (rules.tick_step_ms as f32 / 1000.0) * rules.meter_rate_scale;
+        // a night's sleep slows everything down, so a pet left overnight is not starved
+        let asleep_at_night = bedtime.night && self.flags.sleeping;
+        let slow = if asleep_at_night {
+            rules.night_metabolism
+        } else {
+            1.0
+        };
 
         let decay = rules
             .decay_by_stage
-            .get(&self.pet.stage)
+            .get(&self.stage)
             .copied()
             .unwrap_or(DecayRates {
                 hunger: 1.0,
                 happiness: 0.8,
                 energy: 0.7,
                 hygiene: 0.6,
-            });
+            })
+            .scaled(self.constitution);
 
-        self.pet.meters.hunger = (self.pet.meters.hunger - decay.hunger * dt).clamp(0.0, 100.0);
-        self.pet.meters.happiness =
-            (self.pet.meters.happiness - decay.happiness * dt).clamp(0.0, 100.0);
+        self.meters.hunger = (self.meters.hunger - decay.hunger * slow * dt).clamp(0.0, 100.0);
+        if !asleep_at_night {
+            self.meters.happiness =
+                (self.meters.happiness - decay.happiness * dt).clamp(0.0, 100.0);
+        }
 
-        if !self.pet.flags.sleeping {
-            self.pet.meters.energy = (self.pet.meters.energy - decay.energy * dt).clamp(0.0, 100.0);
+        if !self.flags.sleeping {
+            self.meters.energy = (self.meters.energy - decay.energy * dt).clamp(0.0, 100.0);
         } else {
             let recover = 9.0;
-            self.pet.meters.energy = (self.pet.meters.energy + recover * dt).clamp(0.0, 100.0);
-            let sleep_hunger = 0.7;
-            self.pet.meters.hunger = (self.pet.meters.hunger - sleep_hunger * dt).clamp(0.0, 100.0);
-            if self.pet.meters.energy >= 99.5 && self.rng.roll(0.02) {
-                self.pet.flags.sleeping = false;
+            self.meters.energy = (self.meters.energy + recover * dt).clamp(0.0, 100.0);
+            let sleep_hunger = if asleep_at_night { 0.0 } else { 0.7 };
+            self.meters.hunger = (self.meters.hunger - sleep_hunger * dt).clamp(0.0, 100.0);
+            // scheduled sleep lasts until morning
+            if !bedtime.night && self.meters.energy >= 99.5 && self.rng.roll(0.02) {
+                self.flags.sleeping = false;
+                self.log(now, JournalEvent::Woke);
             }
         }
 
-        self.pet.meters.hygiene = (self.pet.meters.hygiene - decay.hygiene * dt).clamp(0.0, 100.0);
+        if company != 0.0 && !self.flags.sleeping {
+            self.meters.happiness = (self.meters.happiness + company * dt).clamp(0.0, 100.0);
+        }
 
-        if self.pet.flags.has_poop {
+        if bedtime.late && !self.flags.sleeping {
+            // kept up past bedtime: cranky, worn out and a little run down
+            self.meters.happiness = (self.meters.happiness - 1.5 * dt).clamp(0.0, 100.0);
+            self.meters.energy = (self.meters.energy - 1.0 * dt).clamp(0.0, 100.0);
+            self.meters.health = (self.meters.health - 0.5 * dt).clamp(0.0, 100.0);
+            if !self.flags.kept_up {
+                self.flags.kept_up = true;
+                self.log(now, JournalEvent::KeptUp);
+            }
+        }
+
+        self.meters.hygiene = (self.meters.hygiene - decay.hygiene * slow * dt).clamp(0.0, 100.0);
+
+        if self.flags.has_poop {
             let poop_penalty = 2.0;
-            self.pet.meters.hygiene =
-                (self.pet.meters.hygiene - poop_penalty * dt).clamp(0.0, 100.0);
-            self.pet.flags.dirty = true;
+            self.meters.hygiene = (self.meters.hygiene - poop_penalty * dt).clamp(0.0, 100.0);
+            self.flags.dirty = true;
         }
 
         // sickness chance (simple)
         let mut sick_chance = 0.0;
-        if self.pet.meters.hygiene < 20.0 {
+        if self.meters.hygiene < 20.0 {
             sick_chance += 0.0025;
         }
-        if self.pet.meters.hunger < 10.0 {
+        if self.meters.hunger < 10.0 {
             sick_chance += 0.0035;
         }
         sick_chance *= rules.meter_rate_scale;
-        if !self.pet.flags.sick && self.rng.roll(sick_chance) {
-            self.pet.flags.sick = true;
-            self.pet.history.sickness_events += 1;
+        if !self.flags.sick && self.rng.roll(sick_chance) {
+            self.flags.sick = true;
+            self.history.sickness_events += 1;
+            self.log(now, JournalEvent::FellSick);
         }
-        if self.pet.flags.sick {
-            self.pet.meters.health = (self.pet.meters.health - 2.6 * dt).clamp(0.0, 100.0);
-            self.pet.meters.happiness = (self.pet.meters.happiness - 1.6 * dt).clamp(0.0, 100.0);
+        if self.flags.sick {
+            self.meters.health = (self.meters.health - 2.6 * dt).clamp(0.0, 100.0);
+            self.meters.happiness = (self.meters.happiness - 1.6 * dt).clamp(0.0, 100.0);
         } else {
             // tiny passive recovery
-            self.pet.meters.health = (self.pet.meters.health + 0.25 * dt).clamp(0.0, 100.0);
+            self.meters.health = (self.meters.health + 0.25 * dt).clamp(0.0, 100.0);
         }
 
         // attention calls
-        let needs_attention = self.pet.meters.hunger < 25.0 || self.pet.meters.happiness < 25.0;
+        let needs_attention = self.meters.hunger < 25.0 || self.meters.happiness < 25.0;
         let cooldown_ticks = (10_000u64 / rules.tick_step_ms).max(1); // 10s
         if needs_attention
-            && (self.sim_ticks.saturating_sub(self.last_action_at_tick) > cooldown_ticks)
+            && (self.age_ticks.saturating_sub(self.last_action_at_tick) > cooldown_ticks)
             && self.rng.roll(0.01)
         {
-            self.pet.flags.attention_call = true;
+            if !self.flags.attention_call {
+                self.attention_at_tick = self.age_ticks;
+                self.log(now, JournalEvent::AttentionCall);
+            }
+            self.flags.attention_call = true;
+        }
+
+        // a call left unanswered for a minute counts as neglect
+        let neglect_ticks = (60_000u64 / rules.tick_step_ms).max(1);
+        if self.flags.attention_call
+            && self.age_ticks.saturating_sub(self.attention_at_tick) > neglect_ticks
+            && self.age_ticks.saturating_sub(self.last_action_at_tick) > neglect_ticks
+        {
+            self.flags.attention_call = false;
+            self.history.neglected_events += 1;
+            self.log(now, JournalEvent::IgnoredCall);
         }
 
-        self.pet.mood = derive_mood(&self.pet.meters, &self.pet.flags);
+        self.mood = derive_mood(&self.meters, &self.flags);
 
-        // evolution (very simple thresholds)
-        self.maybe_evolve(rules);
+        // evolution: timeline decides when, care decides into what
+        self.maybe_evolve(rules, now);
 
         // neglect / death
-        if self.pet.meters.health <= 0.1 {
-            self.pet.flags.dead = true;
+        if self.meters.health <= 0.1 {
+            self.flags.dead = true;
+            self.cause_of_death = Some(DeathCause::Illness);
+        } else if self.stage == LifeStage::Elder
+            && self.lifespan_ticks > 0
+            && self.age_ticks >= self.lifespan_ticks
+        {
+            self.flags.dead = true;
+            self.cause_of_death = Some(DeathCause::OldAge);
+            self.mood = Mood::Sleepy;
+        }
+        if let Some(cause) = self.cause_of_death.filter(|_| self.flags.dead) {
+            self.log(now, JournalEvent::Died { cause });
         }
 
         // stage-specific slow weight drift
-        if self.pet.meters.hunger < 10.0 && self.rng.roll(0.01) {
-            self.pet.meters.weight = (self.pet.meters.weight - 0.2).clamp(0.0, 200.0);
+        if self.meters.hunger < 10.0 && self.rng.roll(0.01) {
+            self.meters.weight = (self.meters.weight - 0.2).clamp(0.0, 200.0);
         }
+
+        self.journal.maybe_sample(self.age_ticks, &self.meters);
     }
 
-    fn maybe_evolve(&mut self, rules: &Rules) {
-        let ticks = self.pet.age_ticks;
+    fn maybe_evolve(&mut self, rules: &Rules, now: DateTime<Utc>) {
+        let ticks = self.age_ticks;
 
         let t_egg = (20_000u64 / rules.tick_step_ms).max(1); // ~20s
         let t_baby = (90_000u64 / rules.tick_step_ms).max(1); // ~90s
         let t_child = (240_000u64 / rules.tick_step_ms).max(1); // ~4m
         let t_teen = (480_000u64 / rules.tick_step_ms).max(1); // ~8m
+        let t_adult = (1_800_000u64 / rules.tick_step_ms).max(1); // ~30m
 
-        let next = match self.pet.stage {
+        let next = match self.stage {
             LifeStage::Egg if ticks >= t_egg => Some(LifeStage::Baby),
             LifeStage::Baby if ticks >= t_baby => Some(LifeStage::Child),
             LifeStage::Child if ticks >= t_child => Some(LifeStage::Teen),
             LifeStage::Teen if ticks >= t_teen => Some(LifeStage::Adult),
+            LifeStage::Adult if ticks >= t_adult => Some(LifeStage::Elder),
             _ => None,
         };
 
         if let Some(ns) = next {
-            self.pet.stage = ns;
+            let care = self.history.since(&self.stage_start);
+            self.species_id = branch_species(ns, &care).to_string();
+            self.stage = ns;
+            self.stage_start = self.history.clone();
+            if ns == LifeStage::Elder {
+                self.lifespan_ticks = ticks + elder_years_ticks(&self.history, rules);
+            }
+            self.log(
+                now,
+                JournalEvent::Evolved {
+                    stage: ns,
+                    species: self.species_id.clone(),
+                },
+            );
             // small stage bonus
-            self.pet.meters.happiness = (self.pet.meters.happiness + 8.0).clamp(0.0, 100.0);
-            self.pet.meters.health = (self.pet.meters.health + 8.0).clamp(0.0, 100.0);
+            self.meters.happiness = (self.meters.happiness + 8.0).clamp(0.0, 100.0);
+            self.meters.health = (self.meters.health + 8.0).clamp(0.0, 100.0);
+        }
+    }
+}
+
+// Species for the stage being entered, chosen from care during the stage just finished.
+fn branch_species(next: LifeStage, care: &History) -> &'static str {
+    let neglected = care.neglected_events >= 2 || care.sickness_events >= 2;
+    let pampered = care.neglected_events == 0 && care.sickness_events == 0;
+    let strict = care.discipline_events >= 2 && care.discipline_events >= care.play_events;
+    let playful = care.play_events >= 3 && care.play_events > care.discipline_events;
+
+    match next {
+        LifeStage::Egg => "default",
+        LifeStage::Baby => "tot",
+        LifeStage::Child => {
+            if neglected {
+                "grub"
+            } else {
+                "sprig"
+            }
+        }
+        LifeStage::Teen => {
+            if neglected {
+                "mope"
+            } else if strict {
+                "scout"
+            } else if playful {
+                "imp"
+            } else {
+                "sprout"
+            }
+        }
+        LifeStage::Adult => {
+            if care.sickness_events >= 2 {
+                "wisp"
+            } else if neglected {
+                "grumble"
+            } else if pampered && strict {
+                "lumen"
+            } else if strict {
+                "warden"
+            } else if playful {
+                "jester"
+            } else {
+                "bloom"
+            }
+        }
+        LifeStage::Elder => {
+            if neglected {
+                "husk"
+            } else {
+                "sage"
+            }
         }
     }
 }
 
+// How long the Elder stage lasts: ~30m, stretched or cut by up to 15m of lifetime care.
+fn elder_years_ticks(lifetime: &History, rules: &Rules) -> u64 {
+    let good = lifetime.play_events + lifetime.clean_events + lifetime.discipline_events
+        + lifetime.feed_events / 2;
+    let bad = lifetime.neglected_events * 3 + lifetime.sickness_events * 4;
+    let bonus_min = (good as i64 - bad as i64).clamp(-15, 15);
+    let ms = (30 + bonus_min) as u64 * 60_000;
+    (ms / rules.tick_step_ms).max(1)
+}
+
 fn derive_mood(m: &Meters, f: &Flags) -> Mood {
     if f.dead {
         return Mood::Sad;
@@ -281,14 +830,16 @@ pub(crate) fn catch_up(
     let max_elapsed = ChronoDuration::seconds(rules.catchup_max_secs.max(0));
     let elapsed = elapsed.clamp(ChronoDuration::zero(), max_elapsed);
 
-    let mut summary = CatchupSummary::new();
+    let mut summary = CatchupSummary::new(state);
+    // the schedule follows the clock from when the player left
+    state.clock = last_seen;
 
     let tick_step = ChronoDuration::milliseconds(rules.tick_step_ms as i64);
     let catch_step = ChronoDuration::milliseconds(rules.catchup_step_ms as i64);
 
     let mut remaining = elapsed;
 
-    while remaining > ChronoDuration::zero() && !state.pet.flags.dead {
+    while remaining > ChronoDuration::zero() && !state.all_dead() {
         let step = if remaining < catch_step {
             remaining
         } else {
@@ -301,13 +852,326 @@ pub(crate) fn catch_up(
         for _ in 0..ticks {
             state.tick_fixed_step(rules);
             summary.record(state);
-            if state.pet.flags.dead {
+            if state.all_dead() {
                 break;
             }
         }
-        remaining =
-            remaining - ChronoDuration::milliseconds((ticks as i64) * tick_step.num_milliseconds());
+        remaining -= ChronoDuration::milliseconds((ticks as i64) * tick_step.num_milliseconds());
     }
 
     summary
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::GameKey;
+
+    fn snapshot(st: &GameState) -> serde_json::Value {
+        serde_json::to_value(st).unwrap()
+    }
+
+    #[test]
+    fn catch_up_matches_live_ticking() {
+        let rules = Rules::default();
+        let last_seen = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        // long enough to hatch, grow and get hungry; not a whole step multiple
+        let away = ChronoDuration::milliseconds(45 * 60 * 1000 + 2_250);
+
+        let mut caught_up = GameState::new(7);
+        let summary = catch_up(&mut caught_up, last_seen, last_seen + away, &rules);
+
+        let mut live = GameState::new(7);
+        live.clock = last_seen;
+        let ticks = away.num_milliseconds() as u64 / rules.tick_step_ms;
+        for _ in 0..ticks {
+            live.tick_fixed_step(&rules);
+        }
+
+        assert_eq!(summary.ticks_simulated, ticks);
+        assert_eq!(snapshot(&caught_up), snapshot(&live));
+    }
+
+    #[test]
+    fn catch_up_is_capped_and_ignores_clock_skew() {
+        let rules = Rules {
+            catchup_max_secs: 60,
+            ..Rules::default()
+        };
+        let now = Utc::now();
+
+        let mut st = GameState::new(1);
+        let summary = catch_up(&mut st, now - ChronoDuration::days(3), now, &rules);
+        assert_eq!(summary.ticks_simulated, 60_000 / rules.tick_step_ms);
+
+        let mut st = GameState::new(1);
+        let summary = catch_up(&mut st, now + ChronoDuration::hours(1), now, &rules);
+        assert_eq!(summary.ticks_simulated, 0);
+        assert_eq!(st.sim_ticks, 0);
+    }
+
+    #[test]
+    fn meters_stay_in_bounds() {
+        let rules = Rules::default();
+        let foods = FoodCatalog::default();
+        let actions = [
+            PlayerAction::Feed("cake".to_string()),
+            PlayerAction::Feed("coffee".to_string()),
+            PlayerAction::PlayAny,
+            PlayerAction::GameInput(GameKey::Up),
+            PlayerAction::GameInput(GameKey::Press),
+            PlayerAction::Clean,
+            PlayerAction::Medicine,
+            PlayerAction::SleepToggle,
+            PlayerAction::Discipline,
+        ];
+
+        for seed in 0..8u64 {
+            let mut st = GameState::new(seed);
+            // seed 0 is left alone to starve; the rest get pestered with care
+            for tick in 0..20_000u64 {
+                if seed > 0 && tick.is_multiple_of(7 + seed * 3) {
+                    let pick = (st.pet_mut().rng.next_u64() % actions.len() as u64) as usize;
+                    st.apply(actions[pick].clone(), &foods);
+                }
+                st.tick_live(&rules);
+
+                let m = st.pet().meters;
+                for v in [
+                    m.hunger,
+                    m.happiness,
+                    m.health,
+                    m.energy,
+                    m.hygiene,
+                    m.discipline,
+                    m.bond,
+                ] {
+                    assert!((0.0..=100.0).contains(&v), "seed {seed} tick {tick}: {m:?}");
+                }
+                assert!((0.0..=200.0).contains(&m.weight), "seed {seed}: {m:?}");
+                if st.pet().flags.dead {
+                    break;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn evolves_on_schedule() {
+        let rules = Rules::default();
+        let foods = FoodCatalog::default();
+        let secs = |s: u64| s * 1000 / rules.tick_step_ms;
+        let schedule = [
+            (secs(20), LifeStage::Baby),
+            (secs(90), LifeStage::Child),
+            (secs(240), LifeStage::Teen),
+            (secs(480), LifeStage::Adult),
+            (secs(1800), LifeStage::Elder),
+        ];
+
+        let mut st = GameState::new(3);
+        let mut expect = LifeStage::Egg;
+        for (at, next) in schedule {
+            while st.pet().age_ticks < at - 1 {
+                // keep it fed and clean so it lives to see every stage
+                if st.pet().age_ticks.is_multiple_of(400) {
+                    st.apply(PlayerAction::Feed("rice".to_string()), &foods);
+                    st.apply(PlayerAction::Clean, &foods);
+                    st.apply(PlayerAction::Medicine, &foods);
+                }
+                st.tick_fixed_step(&rules);
+                assert_eq!(
+                    st.pet().stage,
+                    expect,
+                    "early at tick {}",
+                    st.pet().age_ticks
+                );
+            }
+            st.tick_fixed_step(&rules);
+            assert_eq!(st.pet().stage, next, "tick {}", st.pet().age_ticks);
+            assert!(!st.pet().flags.dead);
+            expect = next;
+        }
+        assert!(st.pet().lifespan_ticks > st.pet().age_ticks);
+    }
+
+    #[test]
+    fn catch_up_advances_the_whole_household() {
+        let rules = Rules::default();
+        let foods = FoodCatalog::default();
+        let now = Utc::now();
+
+        let mut st = GameState::new(11);
+        st.apply(PlayerAction::AdoptEgg { seed: 12 }, &foods);
+        assert_eq!(st.selected, 1);
+        st.tick_fixed_step(&rules);
+        st.apply(PlayerAction::DebugKill, &foods);
+
+        let summary = catch_up(&mut st, now - ChronoDuration::minutes(10), now, &rules);
+        assert_eq!(summary.pets.len(), 2);
+        assert_eq!(st.pets[0].age_ticks, summary.ticks_simulated + 1);
+        assert_eq!(st.pets[1].age_ticks, 1);
+    }
+
+    #[test]
+    fn a_happy_housemate_cheers_the_others_up() {
+        let rules = Rules::default();
+        let foods = FoodCatalog::default();
+
+        let mut alone = GameState::new(21);
+        let mut paired = GameState::new(21);
+        paired.apply(PlayerAction::AdoptEgg { seed: 22 }, &foods);
+        paired.pets[1].mood = Mood::Happy;
+        paired.pets[1].meters.hunger = 100.0;
+        paired.pets[1].meters.happiness = 100.0;
+        for _ in 0..100 {
+            alone.tick_fixed_step(&rules);
+            paired.tick_fixed_step(&rules);
+        }
+        assert!(paired.pets[0].meters.happiness > alone.pets[0].meters.happiness);
+
+        paired.pets[1].flags.sick = true;
+        let before = paired.pets[0].meters.happiness - alone.pets[0].meters.happiness;
+        for _ in 0..100 {
+            alone.tick_fixed_step(&rules);
+            paired.tick_fixed_step(&rules);
+        }
+        let after = paired.pets[0].meters.happiness - alone.pets[0].meters.happiness;
+        assert!(after < before);
+    }
+
+    #[test]
+    fn a_pet_left_overnight_sleeps_through_to_morning() {
+        let rules = Rules::default();
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let schedule = Schedule {
+            bedtime_hour: 21,
+            wake_hour: 7,
+            utc_offset_secs: 2 * 3600,
+        };
+        assert!(!schedule.is_night(at("2024-05-01T18:59:00Z")));
+        assert!(schedule.is_night(at("2024-05-01T19:00:00Z")));
+        assert!(schedule.is_night(at("2024-05-02T04:59:00Z")));
+        assert!(!schedule.is_night(at("2024-05-02T05:00:00Z")));
+
+        // an egg left alone in the evening is asleep soon after bedtime, and the night
+        // barely touches its meters
+        let mut st = GameState::new(4);
+        st.schedule = Some(schedule);
+        catch_up(
+            &mut st,
+            at("2024-05-01T18:50:00Z"),
+            at("2024-05-01T19:40:00Z"),
+            &rules,
+        );
+        let mut unscheduled = GameState::new(4);
+        catch_up(
+            &mut unscheduled,
+            at("2024-05-01T18:50:00Z"),
+            at("2024-05-01T19:40:00Z"),
+            &rules,
+        );
+        let pet = st.pet();
+        assert!(pet.flags.sleeping);
+        assert!(!pet.flags.kept_up);
+        assert!(pet.meters.hunger > unscheduled.pet().meters.hunger + 20.0);
+
+        // it sleeps until the wake-up hour, then gets up
+        st.clock = at("2024-05-02T04:58:00Z");
+        for _ in 0..120 {
+            st.tick_fixed_step(&rules);
+            assert!(st.pet().flags.sleeping);
+        }
+        for _ in 0..120 {
+            st.tick_fixed_step(&rules);
+        }
+        assert!(!st.pet().flags.sleeping);
+
+        // lights out puts an awake pet straight to bed; waking it later costs it
+        let foods = FoodCatalog::default();
+        st.clock = at("2024-05-02T20:00:00Z");
+        st.apply(PlayerAction::LightsToggle, &foods);
+        st.tick_fixed_step(&rules);
+        assert!(st.pet().flags.sleeping);
+        st.apply(PlayerAction::LightsToggle, &foods);
+        st.apply(PlayerAction::SleepToggle, &foods);
+        let before = st.pet().meters.happiness;
+        st.tick_fixed_step(&rules);
+        assert!(!st.pet().flags.sleeping);
+        assert!(st.pet().flags.kept_up);
+        assert!(st.pet().meters.happiness < before);
+    }
+
+    #[test]
+    fn elder_dies_of_old_age_at_lifespan() {
+        let rules = Rules::default();
+        let mut st = GameState::new(5);
+        st.pet_mut().stage = LifeStage::Elder;
+        st.pet_mut().age_ticks = 10_000;
+        st.pet_mut().lifespan_ticks = 10_010;
+        for _ in 0..9 {
+            st.tick_live(&rules);
+            assert!(!st.pet().flags.dead);
+        }
+        st.tick_live(&rules);
+        assert!(st.pet().flags.dead);
+        assert_eq!(st.pet().cause_of_death, Some(DeathCause::OldAge));
+        assert!(matches!(st.scene, Scene::Farewell));
+    }
+
+    #[test]
+    fn the_dead_are_remembered_and_their_eggs_take_after_them() {
+        let rules = Rules::default();
+        let foods = FoodCatalog::default();
+        let mut st = GameState::new(5);
+        st.pet_mut().stage = LifeStage::Elder;
+        st.pet_mut().age_ticks = 10_000;
+        st.pet_mut().lifespan_ticks = 10_001;
+        st.pet_mut().history.feed_events = 12;
+        st.tick_live(&rules);
+        st.tick_live(&rules);
+        assert_eq!(st.graveyard.len(), 1);
+        let grave = &st.graveyard[0];
+        assert_eq!(
+            (grave.stage, grave.cause),
+            (LifeStage::Elder, Some(DeathCause::OldAge))
+        );
+        assert_eq!(grave.age_secs, 10_001 * rules.tick_step_ms / 1000);
+        assert_eq!(grave.history.feed_events, 12);
+
+        // a new household keeps the graveyard, and the egg inherits a hardier constitution
+        st.apply(
+            PlayerAction::Hatch {
+                seed: 6,
+                rules_preset: "classic".to_string(),
+                inherit: true,
+            },
+            &foods,
+        );
+        assert_eq!(st.graveyard.len(), 1);
+        let egg = st.pet();
+        assert_eq!(egg.generation, 2);
+        assert!((0.75..=1.25).contains(&egg.constitution.hunger));
+        assert_ne!(egg.constitution.hunger, 1.0);
+
+        // a housemate dying outside the sim gets its memorial on the next tick
+        st.apply(PlayerAction::AdoptEgg { seed: 7 }, &foods);
+        st.apply(PlayerAction::DebugKill, &foods);
+        st.tick_live(&rules);
+        assert_eq!(st.graveyard.len(), 2);
+        st.tick_live(&rules);
+        assert_eq!(st.graveyard.len(), 2);
+        st.apply(
+            PlayerAction::Hatch {
+                seed: 8,
+                rules_preset: "classic".to_string(),
+                inherit: false,
+            },
+            &foods,
+        );
+        assert_eq!(st.pet().generation, 1);
+        assert_eq!(st.pet().constitution.hunger, 1.0);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::model::{GameState, LifeStage, Mood};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+const BUILTIN_SKINS: [(&str, &str); 2] = [
+    ("default", include_str!("../skins/default/skin.json")),
+    ("cat", include_str!("../skins/cat/skin.json")),
+];
+
+const SPRITE_MAX: usize = 96;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub(crate) enum LayerWhen {
+    Awake,
+    Sleeping,
+    Sick,
+    Dirty,
+    Attention,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub(crate) enum LayerShape {
+    // Soft-edged filled circle (alpha falls off towards the rim).
+    Disc {
+        radius: i32,
+        color: [u8; 4],
+    },
+    // Text-art sprite: each char is looked up in `palette`; ' ' and '.' are transparent.
+    // Rows are given inline or read from `file` (relative to the skin directory).
+    Sprite {
+        #[serde(default)]
+        rows: Vec<String>,
+        #[serde(default)]
+        file: Option<String>,
+        #[serde(default)]
+        palette: BTreeMap<char, [u8; 4]>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct SkinLayer {
+    #[serde(default)]
+    pub(crate) z: i32,
+    #[serde(default = "default_anchor")]
+    pub(crate) anchor: String,
+    // empty = every stage / every mood
+    #[serde(default)]
+    pub(crate) stages: Vec<LifeStage>,
+    #[serde(default)]
+    pub(crate) moods: Vec<Mood>,
+    #[serde(default)]
+    pub(crate) when: Option<LayerWhen>,
+    // replace rgb with the current mood color
+    #[serde(default)]
+    pub(crate) mood_color: bool,
+    pub(crate) shape: LayerShape,
+}
+
+fn default_anchor() -> String {
+    "center".to_string()
+}
+
+impl SkinLayer {
+    pub(crate) fn applies(&self, st: &GameState) -> bool {
+        if !self.stages.is_empty() && !self.stages.contains(&st.pet().stage) {
+            return false;
+        }
+        if !self.moods.is_empty() && !self.moods.contains(&st.pet().mood) {
+            return false;
+        }
+        let f = &st.pet().flags;
+        match self.when {
+            None => true,
+            Some(LayerWhen::Awake) => !f.sleeping,
+            Some(LayerWhen::Sleeping) => f.sleeping,
+            Some(LayerWhen::Sick) => f.sick,
+            Some(LayerWhen::Dirty) => f.dirty || f.has_poop,
+            Some(LayerWhen::Attention) => f.attention_call,
+        }
+    }
+
+    fn stages(&self) -> &[LifeStage] {
+        if self.stages.is_empty() {
+            &LifeStage::ALL
+        } else {
+            &self.stages
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct Skin {
+    pub(crate) name: String,
+    // Named anchor points per stage, in braille subpixels relative to the pet center.
+    // "center" is always available at (0, 0).
+    #[serde(default)]
+    pub(crate) anchors: BTreeMap<LifeStage, BTreeMap<String, [i32; 2]>>,
+    pub(crate) layers: Vec<SkinLayer>,
+}
+
+impl Skin {
+    pub(crate) fn anchor(&self, stage: LifeStage, name: &str) -> (i32, i32) {
+        self.anchors
+            .get(&stage)
+            .and_then(|m| m.get(name))
+            .map(|a| (a[0], a[1]))
+            .unwrap_or((0, 0))
+    }
+
+    // Layers that apply to the current pet, in draw order (lowest z first).
+    pub(crate) fn visible_layers(&self, st: &GameState) -> Vec<&SkinLayer> {
+        let mut out: Vec<&SkinLayer> = self.layers.iter().filter(|l| l.applies(st)).collect();
+        out.sort_by_key(|l| l.z);
+        out
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.layers.is_empty() {
+            bail!("no layers");
+        }
+        for (i, layer) in self.layers.iter().enumerate() {
+            if layer.anchor != "center" {
+                for stage in layer.stages() {
+                    let known = self
+                        .anchors
+                        .get(stage)
+                        .is_some_and(|m| m.contains_key(&layer.anchor));
+                    if !known {
+                        bail!(
+                            "layer {i}: anchor '{}' is not defined for {:?}",
+                            layer.anchor,
+                            stage
+                        );
+                    }
+                }
+            }
+            match &layer.shape {
+                LayerShape::Disc { radius, .. } => {
+                    if !(0..=SPRITE_MAX as i32 / 2).contains(radius) {
+                        bail!("layer {i}: disc radius {radius} out of range");
+                    }
+                }
+                LayerShape::Sprite { rows, palette, .. } => {
+                    if rows.is_empty() {
+                        bail!("layer {i}: sprite has no rows");
+                    }
+                    if rows.len() > SPRITE_MAX
+                        || rows.iter().any(|r| r.chars().count() > SPRITE_MAX)
+                    {
+                        bail!("layer {i}: sprite larger than {SPRITE_MAX}x{SPRITE_MAX}");
+                    }
+                    for ch in rows.iter().flat_map(|r| r.chars()) {
+                        if ch != ' ' && ch != '.' && !palette.contains_key(&ch) {
+                            bail!("layer {i}: '{ch}' is missing from the palette");
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_skin(json: &str, dir: Option<&Path>) -> Result<Skin> {
+    let mut skin: Skin = serde_json::from_str(json)?;
+    for layer in &mut skin.layers {
+        if let LayerShape::Sprite { rows, file, .. } = &mut layer.shape {
+            if let Some(file) = file.as_deref() {
+                let dir = dir.context("built-in skins cannot reference sprite files")?;
+                let path = dir.join(file);
+                let text = fs::read_to_string(&path)
+                    .with_context(|| format!("reading {}", path.display()))?;
+                *rows = text.lines().map(|l| l.to_string()).collect();
+            }
+        }
+    }
+    skin.validate()?;
+    Ok(skin)
+}
+
+pub(crate) fn builtin_skin(name: &str) -> Option<Skin> {
+    BUILTIN_SKINS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .and_then(|(_, json)| parse_skin(json, None).ok())
+}
+
+// Built-in skin names followed by any `<skins_dir>/<name>/skin.json` on disk.
+pub(crate) fn available_skins(skins_dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_SKINS.iter().map(|(n, _)| n.to_string()).collect();
+    let mut user = Vec::new();
+    if let Ok(rd) = fs::read_dir(skins_dir) {
+        for entry in rd.flatten() {
+            let path = entry.path();
+            if path.join("skin.json").is_file() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    if !names.iter().any(|n| n == name) {
+                        user.push(name.to_string());
+                    }
+                }
+            }
+        }
+    }
+    user.sort();
+    names.extend(user);
+    names
+}
+
+// Resolve `name` to a skin. A skin directory on disk shadows a built-in of the same
+// name. Anything missing or malformed falls back to the built-in default, and the
+// returned note says why.
+pub(crate) fn load_skin(skins_dir: &Path, name: &str) -> (Skin, Option<String>) {
+    let dir = skins_dir.join(name);
+    let manifest = dir.join("skin.json");
+    let attempt = if manifest.is_file() {
+        fs::read_to_string(&manifest)
+            .map_err(anyhow::Error::from)
+            .and_then(|s| parse_skin(&s, Some(&dir)))
+            .map(Some)
+    } else {
+        Ok(builtin_skin(name))
+    };
+
+    match attempt {
+        Ok(Some(skin)) => (skin, None),
+        Ok(None) => (fallback_skin(), Some(format!("skin '{name}' not found"))),
+        Err(e) => (
+            fallback_skin(),
+            Some(format!("skin '{name}' is malformed: {e:#}")),
+        ),
+    }
+}
+
+fn fallback_skin() -> Skin {
+    builtin_skin("default").expect("built-in default skin is valid")
+}
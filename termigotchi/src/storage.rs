@@ -1,26 +1,263 @@
 use crate::config::atomic_rename;
 use crate::config::Settings;
-use crate::model::{GameState, SaveFile};
-use anyhow::Result;
+use crate::model::{GameState, Rules, SaveFile, SAVE_VERSION};
+use crate::rules::DEFAULT_PRESET;
+use anyhow::{anyhow, bail, Context, Result};
 use chrono::{DateTime, Utc};
-use std::{fs, path::Path};
-
-pub(crate) fn load_or_init_save(
-    path: &Path,
-    settings: &Settings,
-) -> Result<(GameState, Option<DateTime<Utc>>)> {
-    if let Ok(s) = fs::read_to_string(path) {
-        if let Ok(save) = serde_json::from_str::<SaveFile>(&s) {
-            return Ok((save.state, Some(save.last_seen_utc)));
+use serde_json::Value;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+const BACKUP_COUNT: usize = 3;
+// Backups rotate at most this often, so autosaves cannot push out the last good session.
+const BACKUP_EVERY: Duration = Duration::from_secs(60 * 60);
+
+pub(crate) struct LoadedSave {
+    pub(crate) state: GameState,
+    pub(crate) last_seen: Option<DateTime<Utc>>,
+    pub(crate) rules: Option<Rules>,
+    // something went wrong and the player should hear about it
+    pub(crate) notice: Option<String>,
+}
+
+// Each entry upgrades a save from version `i + 1` to `i + 2`.
+const MIGRATIONS: [fn(Value) -> Result<Value>; 3] =
+    [migrate_v1_to_v2, migrate_v2_to_v3, migrate_v3_to_v4];
+
+// v2 tracks care per life stage and how the pet died. Without a snapshot the whole
+// lifetime would count as care for the current stage, so start it from today's totals.
+fn migrate_v1_to_v2(mut v: Value) -> Result<Value> {
+    let pet = v
+        .pointer_mut("/state/pet")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| anyhow!("missing state.pet"))?;
+    let history = pet.get("history").cloned().unwrap_or(Value::Null);
+    pet.entry("stage_start").or_insert(history);
+    let dead = pet
+        .get("flags")
+        .and_then(|f| f.get("dead"))
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if dead {
+        pet.entry("cause_of_death")
+            .or_insert(Value::String("Illness".to_string()));
+    }
+    Ok(v)
+}
+
+// v3 keeps the rules the pet lives under. Everything before ran on the classic defaults.
+fn migrate_v2_to_v3(mut v: Value) -> Result<Value> {
+    let save = v
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("save is not an object"))?;
+    save.entry("rules")
+        .or_insert(serde_json::to_value(Rules::default())?);
+    let state = save
+        .get_mut("state")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| anyhow!("missing state"))?;
+    state
+        .entry("rules_preset")
+        .or_insert(Value::String(DEFAULT_PRESET.to_string()));
+    Ok(v)
+}
+
+// v4 holds a household. The single pet takes its RNG stream and care timestamps along;
+// its age and the old sim clock ran in step, so the timestamps keep their meaning.
+fn migrate_v3_to_v4(mut v: Value) -> Result<Value> {
+    let state = v
+        .pointer_mut("/state")
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| anyhow!("missing state"))?;
+    let mut pet = state
+        .remove("pet")
+        .ok_or_else(|| anyhow!("missing state.pet"))?;
+    let pet_obj = pet
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("state.pet is not an object"))?;
+    for key in ["rng", "last_action_at_tick", "attention_at_tick"] {
+        if let Some(value) = state.remove(key) {
+            pet_obj.insert(key.to_string(), value);
         }
     }
-    Ok((GameState::new(settings.seed), None))
+    state.insert("pets".to_string(), Value::Array(vec![pet]));
+    state.insert("selected".to_string(), Value::from(0));
+    state.insert("hatched".to_string(), Value::from(1));
+    // the recap's shape changed; it is only a courtesy screen
+    if state.get("scene").is_some_and(|s| s.get("Recap").is_some()) {
+        state.insert("scene".to_string(), Value::String("Main".to_string()));
+    }
+    Ok(v)
+}
+
+fn migrate(mut v: Value) -> Result<SaveFile> {
+    let mut version = v
+        .get("version")
+        .and_then(Value::as_u64)
+        .context("missing save version")? as u32;
+    if version == 0 {
+        bail!("invalid save version 0");
+    }
+    if version > SAVE_VERSION {
+        bail!("save version {version} is newer than this build understands ({SAVE_VERSION})");
+    }
+    while version < SAVE_VERSION {
+        v = MIGRATIONS[(version - 1) as usize](v)
+            .with_context(|| format!("migrating save from version {version}"))?;
+        version += 1;
+        v["version"] = Value::from(version);
+    }
+    let mut save: SaveFile = serde_json::from_value(v)?;
+    if save.state.pets.is_empty() {
+        bail!("save has no pets");
+    }
+    save.state.selected = save.state.selected.min(save.state.pets.len() - 1);
+    Ok(save)
+}
+
+fn read_save(path: &Path) -> Result<SaveFile> {
+    let s = fs::read_to_string(path)?;
+    let v: Value = serde_json::from_str(&s).context("not valid JSON")?;
+    migrate(v)
+}
+
+fn stored_version(path: &Path) -> Option<u64> {
+    let s = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<Value>(&s)
+        .ok()?
+        .get("version")?
+        .as_u64()
+}
+
+fn backup_path(path: &Path, n: usize) -> PathBuf {
+    path.with_extension(format!("json.bak{n}"))
+}
+
+pub(crate) fn load_or_init_save(path: &Path, settings: &Settings) -> Result<LoadedSave> {
+    if !path.exists() {
+        return Ok(LoadedSave {
+            state: GameState::new(settings.seed),
+            last_seen: None,
+            rules: None,
+            notice: None,
+        });
+    }
+
+    let err = match read_save(path) {
+        Ok(save) => {
+            return Ok(LoadedSave {
+                state: save.state,
+                last_seen: Some(save.last_seen_utc),
+                rules: Some(save.rules),
+                notice: None,
+            })
+        }
+        Err(e) => e,
+    };
+
+    // A save from a newer build is not corrupt; refuse rather than clobber it.
+    if stored_version(path).is_some_and(|v| v > SAVE_VERSION as u64) {
+        return Err(err.context(format!("refusing to load {}", path.display())));
+    }
+
+    // Move the broken file aside so the next save cannot overwrite it.
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+    let aside = path.with_extension(format!("json.corrupt-{stamp}"));
+    fs::rename(path, &aside)
+        .with_context(|| format!("moving corrupt save to {}", aside.display()))?;
+    let mut notice = format!(
+        "Your save could not be read ({err:#}).\nIt was moved to:\n{}\n",
+        aside.display()
+    );
+
+    for n in 1..=BACKUP_COUNT {
+        let bak = backup_path(path, n);
+        if let Ok(save) = read_save(&bak) {
+            notice.push_str(&format!("\nRestored from backup {}.", bak.display()));
+            return Ok(LoadedSave {
+                state: save.state,
+                last_seen: Some(save.last_seen_utc),
+                rules: Some(save.rules),
+                notice: Some(notice),
+            });
+        }
+    }
+
+    notice.push_str("\nNo usable backup was found; a new egg was started.");
+    Ok(LoadedSave {
+        state: GameState::new(settings.seed),
+        last_seen: None,
+        rules: None,
+        notice: Some(notice),
+    })
+}
+
+fn rotate_backups(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    let newest_age = fs::metadata(backup_path(path, 1))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok());
+    if newest_age.is_some_and(|age| age < BACKUP_EVERY) {
+        return Ok(());
+    }
+    for n in (1..BACKUP_COUNT).rev() {
+        let from = backup_path(path, n);
+        if from.exists() {
+            atomic_rename(&from, &backup_path(path, n + 1))?;
+        }
+    }
+    fs::copy(path, backup_path(path, 1))?;
+    Ok(())
 }
 
 pub(crate) fn save_atomic(path: &Path, save: &SaveFile) -> Result<()> {
     let tmp = path.with_extension("json.tmp");
     let data = serde_json::to_vec_pretty(save)?;
     fs::write(&tmp, data)?;
+    rotate_backups(path)?;
     atomic_rename(&tmp, path)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn backups_rotate_at_most_hourly() {
+        let dir = std::env::temp_dir().join(format!("tg-backups-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("save.json");
+        let save = SaveFile {
+            version: SAVE_VERSION,
+            last_seen_utc: Utc::now(),
+            rules: Rules::default(),
+            state: GameState::new(1),
+        };
+
+        for _ in 0..3 {
+            save_atomic(&path, &save).unwrap();
+        }
+        assert!(backup_path(&path, 1).exists());
+        assert!(!backup_path(&path, 2).exists());
+
+        let earlier = SystemTime::now() - BACKUP_EVERY - Duration::from_secs(1);
+        File::options()
+            .write(true)
+            .open(backup_path(&path, 1))
+            .unwrap()
+            .set_modified(earlier)
+            .unwrap();
+        save_atomic(&path, &save).unwrap();
+        let rotated = backup_path(&path, 2).exists();
+        fs::remove_dir_all(&dir).ok();
+        assert!(rotated);
+    }
+}
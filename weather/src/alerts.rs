@@ -0,0 +1,172 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_local_time, ForecastData, HourRow};
+
+// What an alert rule watches, in the units the forecast comes in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Metric {
+    TempC,
+    WindKph,
+    PrecipProbPct,
+    PrecipMm,
+}
+
+impl Metric {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Metric::TempC => "temp",
+            Metric::WindKph => "wind",
+            Metric::PrecipProbPct => "precip chance",
+            Metric::PrecipMm => "precip",
+        }
+    }
+}
+
+// Fires when `metric` goes above `above` or below `below` at any hour in the next
+// `within_hours`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AlertRule {
+    pub(crate) name: String,
+    pub(crate) metric: Metric,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) above: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) below: Option<f64>,
+    #[serde(default = "default_within_hours")]
+    pub(crate) within_hours: u32,
+}
+
+fn default_within_hours() -> u32 {
+    24
+}
+
+impl AlertRule {
+    fn new(name: &str, metric: Metric, above: Option<f64>, below: Option<f64>, hours: u32) -> Self {
+        AlertRule {
+            name: name.to_string(),
+            metric,
+            above,
+            below,
+            within_hours: hours,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.above.is_none() && self.below.is_none() {
+            return Err(anyhow!("alert '{}' needs `above` or `below`", self.name));
+        }
+        if self.within_hours == 0 {
+            return Err(anyhow!(
+                "alert '{}': `within_hours` must be at least 1",
+                self.name
+            ));
+        }
+        Ok(())
+    }
+
+    fn matches(&self, v: f64) -> bool {
+        self.above.is_some_and(|a| v > a) || self.below.is_some_and(|b| v < b)
+    }
+
+    // The value further past the threshold, for reporting the worst hour.
+    fn worse(&self, a: f64, b: f64) -> f64 {
+        if self.below.is_some() && self.above.is_none() {
+            a.min(b)
+        } else {
+            a.max(b)
+        }
+    }
+}
+
+// Used when the config has no `alerts` at all; `alerts = []` turns them off.
+pub(crate) fn default_rules() -> Vec<AlertRule> {
+    vec![
+        AlertRule::new("Frost", Metric::TempC, None, Some(0.0), 24),
+        AlertRule::new("Wind", Metric::WindKph, Some(50.0), None, 24),
+        AlertRule::new("Rain likely", Metric::PrecipProbPct, Some(80.0), None, 12),
+    ]
+}
+
+pub(crate) fn is_default(rules: &[AlertRule]) -> bool {
+    rules == default_rules()
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Alert {
+    pub(crate) rule: AlertRule,
+    // the first matching hour (local time at the location), the worst value, and how many
+    // hours match
+    pub(crate) first: NaiveDateTime,
+    pub(crate) worst: f64,
+    pub(crate) hours: usize,
+}
+
+// Check every rule against the forecast, looking ahead from `now`. The hourly rows are
+// used where they reach; past them a day's min/max stands in for its hours.
+pub(crate) fn evaluate(rules: &[AlertRule], fc: &ForecastData, now: DateTime<Local>) -> Vec<Alert> {
+    let Some(here_now) = fc.local_now(now) else {
+        return Vec::new();
+    };
+    let this_hour = here_now
+        .date()
+        .and_hms_opt(here_now.hour(), 0, 0)
+        .unwrap_or(here_now);
+
+    let hourly: Vec<(NaiveDateTime, &HourRow)> = fc
+        .hourly
+        .iter()
+        .filter_map(|h| Some((parse_local_time(&h.time_local)?, h)))
+        .collect();
+    let hourly_end = hourly.last().map(|(t, _)| *t);
+
+    let mut out = Vec::new();
+    for rule in rules {
+        let until = this_hour + ChronoDuration::hours(rule.within_hours as i64);
+        let mut hits = hourly
+            .iter()
+            .filter(|(t, _)| *t >= this_hour && *t < until)
+            .map(|(t, h)| {
+                let v = match rule.metric {
+                    Metric::TempC => h.temp_c,
+                    Metric::WindKph => h.wind_kph,
+                    Metric::PrecipProbPct => h.precip_prob_pct,
+                    Metric::PrecipMm => h.precip_mm,
+                };
+                (*t, v, 1)
+            })
+            .filter(|(_, v, _)| rule.matches(*v))
+            .collect::<Vec<_>>();
+
+        // days the hourly rows don't reach, for what the daily rows carry
+        for d in &fc.daily {
+            let start = d.date.and_hms_opt(0, 0, 0).unwrap_or_default();
+            if hourly_end.is_some_and(|end| start <= end) || start >= until {
+                continue;
+            }
+            let v = match rule.metric {
+                Metric::TempC if rule.below.is_some() && rule.matches(d.tmin_c) => d.tmin_c,
+                Metric::TempC => d.tmax_c,
+                Metric::PrecipProbPct => d.precip_prob_max_pct,
+                Metric::WindKph | Metric::PrecipMm => continue,
+            };
+            if rule.matches(v) {
+                let from = start.max(this_hour);
+                let to = until.min(start + ChronoDuration::days(1));
+                hits.push((from, v, (to - from).num_hours() as usize));
+            }
+        }
+
+        if let Some(&(first, v, _)) = hits.first() {
+            out.push(Alert {
+                rule: rule.clone(),
+                first,
+                worst: hits.iter().fold(v, |w, (_, v, _)| rule.worse(w, *v)),
+                hours: hits.iter().map(|(_, _, n)| n).sum(),
+            });
+        }
+    }
+    out
+}
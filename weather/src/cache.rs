@@ -0,0 +1,130 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{geocode::Candidate, ForecastData, Location, RadarData};
+
+// Basemap tiles hardly ever change; refetch them after a month.
+const TILE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+// Last-known forecast, radar, ZIP and place lookups and basemap tiles, so the app can start (and
+// stay useful) without a network.
+#[derive(Debug, Clone)]
+pub(crate) struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    // The platform cache directory (e.g. `~/.cache/weather`), or `None` if there is no
+    // usable home directory.
+    pub(crate) fn open() -> Option<Cache> {
+        let dirs = ProjectDirs::from("", "", "weather")?;
+        Cache::at(dirs.cache_dir().to_path_buf())
+    }
+
+    // A cache in `dir`, created if missing.
+    pub(crate) fn at(dir: PathBuf) -> Option<Cache> {
+        fs::create_dir_all(dir.join("tiles")).ok()?;
+        Some(Cache { dir })
+    }
+
+    pub(crate) fn load_forecast(&self, loc: &Location) -> Option<ForecastData> {
+        read_json(&self.dir.join(format!("forecast_{}.json", coord_key(loc))))
+    }
+
+    pub(crate) fn store_forecast(&self, loc: &Location, fc: &ForecastData) -> Result<()> {
+        write_json(
+            &self.dir.join(format!("forecast_{}.json", coord_key(loc))),
+            fc,
+        )
+    }
+
+    pub(crate) fn load_radar(&self, loc: &Location, zoom: u8) -> Option<RadarData> {
+        read_json(&self.radar_path(loc, zoom))
+    }
+
+    pub(crate) fn store_radar(&self, loc: &Location, rd: &RadarData) -> Result<()> {
+        write_json(&self.radar_path(loc, rd.zoom), rd)
+    }
+
+    pub(crate) fn load_zip(&self, country: &str, postal: &str) -> Option<Location> {
+        read_json(&self.zip_path(country, postal))
+    }
+
+    pub(crate) fn store_zip(&self, country: &str, postal: &str, loc: &Location) -> Result<()> {
+        write_json(&self.zip_path(country, postal), loc)
+    }
+
+    pub(crate) fn load_places(&self, query: &str) -> Option<Vec<Candidate>> {
+        read_json(&self.lookup_path("place", query))
+    }
+
+    pub(crate) fn store_places(&self, query: &str, found: &[Candidate]) -> Result<()> {
+        write_json(&self.lookup_path("place", query), &found)
+    }
+
+    pub(crate) fn load_tile(&self, zoom: u8, x: i32, y: i32) -> Option<Vec<u8>> {
+        let path = self.tile_path(zoom, x, y);
+        let age = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| SystemTime::now().duration_since(t).ok())?;
+        if age > TILE_MAX_AGE {
+            return None;
+        }
+        fs::read(path).ok()
+    }
+
+    pub(crate) fn store_tile(&self, zoom: u8, x: i32, y: i32, png: &[u8]) -> Result<()> {
+        write_atomic(&self.tile_path(zoom, x, y), png)
+    }
+
+    fn radar_path(&self, loc: &Location, zoom: u8) -> PathBuf {
+        self.dir
+            .join(format!("radar_{}_z{}.json", coord_key(loc), zoom))
+    }
+
+    fn zip_path(&self, country: &str, postal: &str) -> PathBuf {
+        self.lookup_path("zip", &format!("{country}_{postal}"))
+    }
+
+    fn lookup_path(&self, kind: &str, key: &str) -> PathBuf {
+        let key: String = key
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{kind}_{}.json", key.to_lowercase()))
+    }
+
+    fn tile_path(&self, zoom: u8, x: i32, y: i32) -> PathBuf {
+        self.dir.join("tiles").join(format!("{zoom}_{x}_{y}.png"))
+    }
+}
+
+fn coord_key(loc: &Location) -> String {
+    format!("{:.4}_{:.4}", loc.lat, loc.lon)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let data = fs::read(path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let data = serde_json::to_vec(value).context("cache encode failed")?;
+    write_atomic(path, &data)
+}
+
+// Write next to the target and rename over it, so a crash never leaves half a file.
+fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, data).with_context(|| format!("cache write failed: {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("cache write failed: {}", path.display()))?;
+    Ok(())
+}
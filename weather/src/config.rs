@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alerts::{self, AlertRule},
+    units::UnitSettings,
+    Location,
+};
+
+// Locations closer than this (in degrees, ~10 m) are the same place.
+const SAME_PLACE_DEG: f64 = 1e-4;
+
+// User settings, read from `config.toml` in the platform config directory (e.g.
+// `~/.config/weather/config.toml`). A missing file means the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Config {
+    // saved locations, in the order the location key cycles through them
+    #[serde(default)]
+    pub(crate) locations: Vec<Location>,
+    // threshold alerts checked against every forecast; the defaults are left out on save so
+    // that saving a location doesn't pin them in the file
+    #[serde(
+        default = "alerts::default_rules",
+        skip_serializing_if = "alerts::is_default"
+    )]
+    pub(crate) alerts: Vec<AlertRule>,
+    // display units; the command line overrides these
+    #[serde(default, skip_serializing_if = "UnitSettings::is_empty")]
+    pub(crate) units: UnitSettings,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            locations: Vec::new(),
+            alerts: alerts::default_rules(),
+            units: UnitSettings::default(),
+        }
+    }
+}
+
+impl Config {
+    pub(crate) fn default_path() -> Option<PathBuf> {
+        let dirs = ProjectDirs::from("", "", "weather")?;
+        Some(dirs.config_dir().join("config.toml"))
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Config> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("config read failed: {}", path.display()))
+            }
+        };
+        let config: Config = toml::from_str(&text)
+            .with_context(|| format!("config parse failed: {}", path.display()))?;
+        for rule in &config.alerts {
+            rule.validate()
+                .with_context(|| format!("config invalid: {}", path.display()))?;
+        }
+        Ok(config)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("config write failed: {}", dir.display()))?;
+        }
+        let text = toml::to_string_pretty(self).context("config encode failed")?;
+        // written beside it and renamed over it, so a crash cannot lose the saved locations
+        let tmp = path.with_extension("toml.tmp");
+        fs::write(&tmp, text).with_context(|| format!("config write failed: {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("config write failed: {}", path.display()))
+    }
+
+    // Index of the saved location at (about) the same spot, if any.
+    pub(crate) fn find(&self, loc: &Location) -> Option<usize> {
+        self.locations.iter().position(|l| same_place(l, loc))
+    }
+
+    // Add a location unless one at the same spot is saved already; returns its index.
+    pub(crate) fn add_location(&mut self, loc: Location) -> usize {
+        match self.find(&loc) {
+            Some(i) => i,
+            None => {
+                self.locations.push(loc);
+                self.locations.len() - 1
+            }
+        }
+    }
+}
+
+pub(crate) fn same_place(a: &Location, b: &Location) -> bool {
+    (a.lat - b.lat).abs() < SAME_PLACE_DEG && (a.lon - b.lon).abs() < SAME_PLACE_DEG
+}
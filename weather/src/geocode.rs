@@ -0,0 +1,207 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::Location;
+
+pub(crate) const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com";
+
+// Matches asked for per search; qualifiers ("Portland, ME") are applied to these.
+const MAX_RESULTS: usize = 20;
+
+// The geocoder only searches by name, so "ME" in "Portland, ME" has to be matched here.
+const US_STATES: [(&str, &str); 51] = [
+    ("AL", "Alabama"),
+    ("AK", "Alaska"),
+    ("AZ", "Arizona"),
+    ("AR", "Arkansas"),
+    ("CA", "California"),
+    ("CO", "Colorado"),
+    ("CT", "Connecticut"),
+    ("DE", "Delaware"),
+    ("DC", "District of Columbia"),
+    ("FL", "Florida"),
+    ("GA", "Georgia"),
+    ("HI", "Hawaii"),
+    ("ID", "Idaho"),
+    ("IL", "Illinois"),
+    ("IN", "Indiana"),
+    ("IA", "Iowa"),
+    ("KS", "Kansas"),
+    ("KY", "Kentucky"),
+    ("LA", "Louisiana"),
+    ("ME", "Maine"),
+    ("MD", "Maryland"),
+    ("MA", "Massachusetts"),
+    ("MI", "Michigan"),
+    ("MN", "Minnesota"),
+    ("MS", "Mississippi"),
+    ("MO", "Missouri"),
+    ("MT", "Montana"),
+    ("NE", "Nebraska"),
+    ("NV", "Nevada"),
+    ("NH", "New Hampshire"),
+    ("NJ", "New Jersey"),
+    ("NM", "New Mexico"),
+    ("NY", "New York"),
+    ("NC", "North Carolina"),
+    ("ND", "North Dakota"),
+    ("OH", "Ohio"),
+    ("OK", "Oklahoma"),
+    ("OR", "Oregon"),
+    ("PA", "Pennsylvania"),
+    ("RI", "Rhode Island"),
+    ("SC", "South Carolina"),
+    ("SD", "South Dakota"),
+    ("TN", "Tennessee"),
+    ("TX", "Texas"),
+    ("UT", "Utah"),
+    ("VT", "Vermont"),
+    ("VA", "Virginia"),
+    ("WA", "Washington"),
+    ("WV", "West Virginia"),
+    ("WI", "Wisconsin"),
+    ("WY", "Wyoming"),
+];
+
+// One place matching a search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Candidate {
+    pub(crate) name: String,
+    // state, province etc.; empty if the geocoder has none
+    pub(crate) region: String,
+    pub(crate) country: String,
+    pub(crate) country_code: String,
+    pub(crate) lat: f64,
+    pub(crate) lon: f64,
+}
+
+impl Candidate {
+    // "Portland, Maine, United States"
+    pub(crate) fn label(&self) -> String {
+        [&self.name, &self.region, &self.country]
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // Named "place, region" like a ZIP lookup, or "place, country" without a region.
+    pub(crate) fn location(&self) -> Location {
+        let within = if self.region.is_empty() || self.region == self.name {
+            &self.country
+        } else {
+            &self.region
+        };
+        Location {
+            name: if within.is_empty() {
+                self.name.clone()
+            } else {
+                format!("{}, {within}", self.name)
+            },
+            lat: self.lat,
+            lon: self.lon,
+        }
+    }
+
+    // Whether `qualifier` (from after a comma in the search) names this place's region or
+    // country: in full, its start, a country code or a US state abbreviation.
+    fn matches(&self, qualifier: &str) -> bool {
+        let q = qualifier.to_lowercase();
+        let state = US_STATES
+            .iter()
+            .find(|(abbr, _)| abbr.eq_ignore_ascii_case(qualifier))
+            .map(|(_, state)| *state);
+        [&self.region, &self.country]
+            .into_iter()
+            .any(|s| s.to_lowercase().starts_with(&q))
+            || self.country_code.eq_ignore_ascii_case(qualifier)
+            || (self.country_code == "US" && state == Some(self.region.as_str()))
+    }
+}
+
+// Open-Meteo's geocoding API, or anything serving the same JSON at `base_url`.
+#[derive(Debug, Clone)]
+pub(crate) struct Geocoder {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl Geocoder {
+    pub(crate) fn new(base_url: &str) -> Geocoder {
+        Geocoder {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+
+    // Places matching `query`, best first. The part before the first comma is the name
+    // searched for; anything after narrows it down by region or country.
+    pub(crate) async fn search(&self, query: &str) -> Result<Vec<Candidate>> {
+        let mut parts = query.split(',').map(str::trim).filter(|s| !s.is_empty());
+        let name = parts.next().ok_or_else(|| anyhow!("empty place name"))?;
+        let qualifiers: Vec<&str> = parts.collect();
+
+        let count = MAX_RESULTS.to_string();
+        let resp = self
+            .client
+            .get(format!("{}/v1/search", self.base_url))
+            .query(&[
+                ("name", name),
+                ("count", &count),
+                ("language", "en"),
+                ("format", "json"),
+            ])
+            .send()
+            .await
+            .context("place search request failed")?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("place search HTTP {}", resp.status()));
+        }
+
+        let gr: GeocodingResp = resp
+            .json()
+            .await
+            .context("place search JSON parse failed")?;
+        Ok(gr
+            .results
+            .into_iter()
+            .map(Candidate::from)
+            .filter(|c| qualifiers.iter().all(|q| c.matches(q)))
+            .collect())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResp {
+    // left out entirely when nothing matches
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    #[serde(default)]
+    admin1: Option<String>,
+    #[serde(default)]
+    country: Option<String>,
+    #[serde(default)]
+    country_code: Option<String>,
+}
+
+impl From<GeocodingResult> for Candidate {
+    fn from(r: GeocodingResult) -> Self {
+        Candidate {
+            name: r.name,
+            region: r.admin1.unwrap_or_default(),
+            country: r.country.unwrap_or_default(),
+            country_code: r.country_code.unwrap_or_default(),
+            lat: r.latitude,
+            lon: r.longitude,
+        }
+    }
+}
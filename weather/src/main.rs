@@ -1,15 +1,17 @@
 use std::{
     collections::HashMap,
     io::{self, Stdout},
+    path::PathBuf,
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Local, NaiveDate};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Timelike};
 use clap::Parser;
 use crossterm::{
     cursor,
-    event::{self, Event, KeyCode, KeyEventKind},
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
     execute,
     terminal::{
         self, BeginSynchronizedUpdate, DisableLineWrap, EnableLineWrap, EndSynchronizedUpdate,
@@ -24,9 +26,25 @@ use ratatui::{
     text::{Line, Span},
     widgets::*,
 };
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::{mpsc, RwLock};
 
+mod alerts;
+mod cache;
+mod config;
+mod geocode;
+mod once;
+mod provider;
+mod units;
+
+use alerts::{Alert, AlertRule, Metric};
+use cache::Cache;
+use config::Config;
+use geocode::{Candidate, Geocoder, GEOCODING_URL};
+use once::OutputFormat;
+use provider::{Fixture, ForecastProvider, OpenMeteo, OPEN_METEO_URL};
+use units::{PrecipUnit, PressureUnit, TempUnit, UnitSettings, Units};
+
 #[derive(Parser, Debug, Clone)]
 #[command(name = "weather")]
 #[command(about = "Terminal weather (Open-Meteo + RainViewer radar)")]
@@ -47,6 +65,23 @@ struct Cli {
     #[arg(long, default_value = "us")]
     country: String,
 
+    /// Place name to search for, e.g. "Portland, ME" or "Paris, France". If provided,
+    /// overrides lat/lon.
+    #[arg(long, conflicts_with = "zip")]
+    place: Option<String>,
+
+    /// Name for the --zip, --place or --lat/--lon location (default: the place name found)
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Add the --zip, --place or --lat/--lon location to the saved locations in the config file
+    #[arg(long, default_value_t = false)]
+    save: bool,
+
+    /// Config file (default: config.toml in the platform config dir, e.g. ~/.config/weather)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
     /// Refresh interval for forecast (minutes)
     #[arg(long, default_value_t = 15)]
     forecast_refresh_min: u64,
@@ -62,33 +97,117 @@ struct Cli {
     /// Force monochrome (no colors)
     #[arg(long, default_value_t = false)]
     mono: bool,
+
+    /// Forecast API base URL (point it at a local stand-in for Open-Meteo)
+    #[arg(long, default_value = OPEN_METEO_URL)]
+    forecast_url: String,
+
+    /// Place search API base URL (point it at a local stand-in for Open-Meteo's geocoder)
+    #[arg(long, default_value = GEOCODING_URL)]
+    geocoder_url: String,
+
+    /// Read the forecast from a recorded Open-Meteo JSON file instead of the network
+    #[arg(long)]
+    forecast_fixture: Option<PathBuf>,
+
+    /// Print the forecast once and exit instead of starting the UI (exit code 3: fetch
+    /// failed, 4: fetch failed and the cached forecast was printed)
+    #[arg(long, default_value_t = false)]
+    once: bool,
+
+    /// Output of --once
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Hours of forecast printed by --once
+    #[arg(long, default_value_t = 6)]
+    hours: usize,
+
+    #[command(flatten)]
+    units: UnitSettings,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Location {
     name: String,
     lat: f64,
     lon: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct ForecastData {
     fetched_at: DateTime<Local>,
+    timezone: String,
     current: CurrentSummary,
     hourly: Vec<HourRow>,
     daily: Vec<DayRow>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct RadarData {
     fetched_at: DateTime<Local>,
-    frame_time_utc: i64,
+    // the point the frames are centered on: the location, or wherever it was panned to
+    lat: f64,
+    lon: f64,
     zoom: u8,
-    cells: Vec<Vec<RadarCell>>,
+    // oldest first: the past frames, then any nowcast frames
+    frames: Vec<RadarFrame>,
     info: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RadarFrame {
+    time_utc: i64,
+    nowcast: bool,
+    // RainViewer's path for the frame, so a refresh can keep the frames it already has
+    #[serde(default)]
+    path: String,
+    cells: Vec<Vec<RadarCell>>,
+}
+
+impl ForecastData {
+    // The clock at the location at `now`: the forecast's "current" time, moved on by the
+    // forecast's age.
+    fn local_now(&self, now: DateTime<Local>) -> Option<NaiveDateTime> {
+        let current = parse_local_time(&self.current.time_local)?;
+        Some(current + (now - self.fetched_at))
+    }
+
+    // Surface pressure change over the three hours to the current reading, in hPa. None
+    // for forecasts cached before pressure was fetched.
+    fn pressure_trend(&self) -> Option<f64> {
+        if self.current.pressure_hpa <= 0.0 {
+            return None;
+        }
+        let now = parse_local_time(&self.current.time_local)?;
+        let then = now.date().and_hms_opt(now.hour(), 0, 0)? - chrono::Duration::hours(3);
+        let past = self
+            .hourly
+            .iter()
+            .find(|h| parse_local_time(&h.time_local) == Some(then))?;
+        Some(self.current.pressure_hpa - past.pressure_hpa)
+    }
+
+    // The daily row for the current reading's date.
+    fn today(&self) -> Option<&DayRow> {
+        let now = parse_local_time(&self.current.time_local)?;
+        self.daily.iter().find(|d| d.date == now.date())
+    }
+}
+
+// Open-Meteo's local times, e.g. "2025-03-14T14:15".
+fn parse_local_time(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").ok()
+}
+
+impl RadarData {
+    // The newest observed (not forecast) frame.
+    fn latest_past(&self) -> usize {
+        self.frames.iter().rposition(|f| !f.nowcast).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct CurrentSummary {
     temp_c: f64,
     wind_kph: f64,
@@ -97,9 +216,25 @@ struct CurrentSummary {
     precip_prob_pct: f64,
     code: i32,
     time_local: String,
+    // added later; absent (zero) in forecasts cached before then
+    #[serde(default)]
+    humidity_pct: f64,
+    #[serde(default)]
+    dew_point_c: f64,
+    #[serde(default)]
+    feels_like_c: f64,
+    #[serde(default)]
+    pressure_hpa: f64,
+    #[serde(default)]
+    uv_index: f64,
+    #[serde(default)]
+    gust_kph: f64,
+    // where the wind blows from, degrees clockwise from north
+    #[serde(default)]
+    wind_dir_deg: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct HourRow {
     time_local: String,
     temp_c: f64,
@@ -108,15 +243,42 @@ struct HourRow {
     wind_kph: f64,
     cloud_pct: f64,
     code: i32,
+    // added later; absent (zero) in forecasts cached before then
+    #[serde(default)]
+    humidity_pct: f64,
+    #[serde(default)]
+    dew_point_c: f64,
+    #[serde(default)]
+    feels_like_c: f64,
+    #[serde(default)]
+    pressure_hpa: f64,
+    #[serde(default)]
+    uv_index: f64,
+    #[serde(default)]
+    gust_kph: f64,
+    // where the wind blows from, degrees clockwise from north
+    #[serde(default)]
+    wind_dir_deg: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct DayRow {
     date: NaiveDate,
     tmax_c: f64,
     tmin_c: f64,
     precip_prob_max_pct: f64,
     code: i32,
+    // added later; absent (empty / zero) in forecasts cached before then
+    #[serde(default)]
+    sunrise_local: String,
+    #[serde(default)]
+    sunset_local: String,
+    #[serde(default)]
+    uv_max: f64,
+    #[serde(default)]
+    gust_max_kph: f64,
+    #[serde(default)]
+    wind_dir_deg: f64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -125,12 +287,20 @@ enum Tab {
     Hourly,
     Daily,
     Radar,
+    Alerts,
     Help,
 }
 
 impl Tab {
     fn all() -> &'static [Tab] {
-        &[Tab::Now, Tab::Hourly, Tab::Daily, Tab::Radar, Tab::Help]
+        &[
+            Tab::Now,
+            Tab::Hourly,
+            Tab::Daily,
+            Tab::Radar,
+            Tab::Alerts,
+            Tab::Help,
+        ]
     }
     fn title(self) -> &'static str {
         match self {
@@ -138,66 +308,270 @@ impl Tab {
             Tab::Hourly => "Hourly",
             Tab::Daily => "Daily",
             Tab::Radar => "Radar",
+            Tab::Alerts => "Alerts",
             Tab::Help => "Help",
         }
     }
 }
 
+// Hours of the hourly charts before and after now.
+const CHART_PAST_H: f64 = 12.0;
+const CHART_AHEAD_H: f64 = 48.0;
+
+// Radar loop speed, and how many frames' time it rests on the newest frame.
+const RADAR_FRAME_EVERY: Duration = Duration::from_millis(500);
+const RADAR_LOOP_HOLD: u8 = 3;
+
+// Radar frames cover RADAR_PX world pixels (256px tiles) square, drawn as RADAR_COLS x
+// RADAR_ROWS braille cells. A pan moves the view a quarter of that, and the new frames
+// are fetched once the keys have been left alone for RADAR_PAN_SETTLE.
+const TILE_PX: f64 = 256.0;
+const RADAR_PX: f64 = 512.0;
+const RADAR_COLS: usize = 76;
+const RADAR_ROWS: usize = 22;
+const RADAR_PAN_PX: f64 = RADAR_PX / 4.0;
+const RADAR_PAN_SETTLE: Duration = Duration::from_millis(600);
+
+// A location and what has been fetched for it. Kept while other locations are shown, so
+// switching back is instant.
 #[derive(Debug)]
-struct AppState {
+struct Place {
     location: Location,
+    forecast: Option<ForecastData>,
+    // worked out when the forecast arrives rather than on every draw
+    alerts: Vec<Alert>,
+    // shared with every RenderState; the frames are large
+    radar: Option<Arc<RadarData>>,
+    forecast_loading: bool,
+    radar_loading: bool,
+    // counts radar fetches, so a slower earlier one cannot land after the latest
+    radar_gen: u64,
+    forecast_error: Option<String>,
+    radar_error: Option<String>,
+}
+
+impl Place {
+    fn new(
+        location: Location,
+        cache: Option<&Cache>,
+        radar_zoom: u8,
+        rules: &[AlertRule],
+    ) -> Place {
+        let forecast = cache.and_then(|c| c.load_forecast(&location));
+        Place {
+            alerts: forecast
+                .as_ref()
+                .map(|fc| alerts::evaluate(rules, fc, Local::now()))
+                .unwrap_or_default(),
+            forecast,
+            radar: cache
+                .and_then(|c| c.load_radar(&location, radar_zoom))
+                .map(Arc::new),
+            location,
+            forecast_loading: false,
+            radar_loading: false,
+            radar_gen: 0,
+            forecast_error: None,
+            radar_error: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AppState {
+    // saved locations, plus the one from the command line if it isn't saved
+    places: Vec<Place>,
+    current: usize,
+    // cursor of the location picker while it is open
+    picker: Option<usize>,
+    // the place search box while it is open
+    search: Option<Search>,
     mono: bool,
     tab: Tab,
-    temp_unit: TempUnit,
-    forecast: Option<ForecastData>,
-    radar: Option<RadarData>,
-    last_error: Option<String>,
+    units: Units,
+    // humidity, dew point, pressure etc. in the Hourly and Daily tables
+    extra_columns: bool,
+    radar_zoom: u8,
+    // radar loop: the frame on screen, whether it is advancing, and how many more ticks
+    // to linger on the newest frame before starting over
+    radar_pos: usize,
+    radar_playing: bool,
+    radar_hold: u8,
+    // where the radar view is panned to (None: the location itself), the crosshair's
+    // offset in cells from the middle of the view, and a counter so only the last of a
+    // run of pans fetches
+    radar_center: Option<(f64, f64)>,
+    radar_cursor: (i16, i16),
+    radar_pan_gen: u64,
+    // data older than its refresh interval is shown as stale
+    forecast_every: Duration,
+    radar_every: Duration,
+    alert_rules: Vec<AlertRule>,
+    cache: Option<Cache>,
+    provider: Arc<dyn ForecastProvider>,
+    geocoder: Geocoder,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Search {
+    query: String,
+    // the matches for `searched` (the query as it was sent), and the cursor in them
+    results: Vec<Candidate>,
+    searched: String,
+    sel: usize,
+    searching: bool,
+    error: Option<String>,
+}
+
+impl Search {
+    fn with_results(query: &str, results: Vec<Candidate>) -> Search {
+        Search {
+            query: query.trim().to_string(),
+            searched: query.trim().to_string(),
+            results,
+            ..Search::default()
+        }
+    }
+
+    // Enter picks the match under the cursor, unless the query was edited since.
+    fn picking(&self) -> bool {
+        !self.results.is_empty() && self.query.trim() == self.searched
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    let loc = resolve_location(&cli).await?;
-    let mut state = AppState {
-        location: loc,
-        mono: cli.mono,
-        tab: Tab::Now,
-        temp_unit: TempUnit::C,
-        forecast: None,
-        radar: None,
-        last_error: None,
+    let provider: Arc<dyn ForecastProvider> = match &cli.forecast_fixture {
+        Some(path) => Arc::new(Fixture::new(path.clone())),
+        None => Arc::new(OpenMeteo::new(&cli.forecast_url)),
+    };
+    // recorded data must never end up standing in for the real thing on the next start
+    let cache = if cli.forecast_fixture.is_some() {
+        None
+    } else {
+        Cache::open()
+    };
+    let config_path = cli.config.clone().or_else(Config::default_path);
+    let mut config = match &config_path {
+        Some(path) => Config::load(path)?,
+        None => Config::default(),
     };
 
-    // Initial fetch (best-effort; UI still starts if one fails)
-    match fetch_forecast(state.location.lat, state.location.lon).await {
-        Ok(fc) => state.forecast = Some(fc),
-        Err(e) => state.last_error = Some(format!("forecast: {e:#}")),
-    }
-    match fetch_radar(state.location.lat, state.location.lon, cli.radar_zoom).await {
-        Ok(rd) => state.radar = Some(rd),
-        Err(e) => state.last_error = Some(format!("radar: {e:#}")),
+    let geocoder = Geocoder::new(&cli.geocoder_url);
+
+    // The location on the command line comes first; without one, the first saved location.
+    let mut locations = config.locations.clone();
+    let (loc, others) = resolve_location(&cli, cache.as_ref(), &geocoder).await?;
+    let current = match loc {
+        Some(loc) if cli.save => {
+            let path = config_path
+                .as_ref()
+                .ok_or_else(|| anyhow!("no config directory to save to (use --config)"))?;
+            let i = config.add_location(loc);
+            config.save(path)?;
+            locations = config.locations.clone();
+            i
+        }
+        Some(loc) => config.find(&loc).unwrap_or_else(|| {
+            locations.push(loc);
+            locations.len() - 1
+        }),
+        None if !locations.is_empty() => 0,
+        None => {
+            return Err(anyhow!(
+                "missing --lat/--lon, --zip or --place (and no saved locations)"
+            ))
+        }
+    };
+    // the command line overrides the config file, which overrides metric
+    let units = cli.units.apply(config.units.apply(Units::default()));
+
+    if cli.once {
+        if !others.is_empty() {
+            eprintln!(
+                "weather: {} places match; using {} (add a region or country to pick another)",
+                others.len(),
+                others[0].label()
+            );
+        }
+        let code = once::run(
+            &mut io::stdout(),
+            provider.as_ref(),
+            cache.as_ref(),
+            &locations[current],
+            &config.alerts,
+            units,
+            cli.format,
+            cli.hours,
+        )
+        .await;
+        std::process::exit(code);
     }
 
+    let radar_zoom = cli.radar_zoom.clamp(1, 10);
+
+    // Start from whatever was cached last time; the refreshers fetch once it goes stale.
+    let places: Vec<Place> = locations
+        .into_iter()
+        .map(|loc| Place::new(loc, cache.as_ref(), radar_zoom, &config.alerts))
+        .collect();
+    let forecast_every = Duration::from_secs(cli.forecast_refresh_min * 60);
+    let radar_every = Duration::from_secs(cli.radar_refresh_min * 60);
+    let shown = &places[current];
+    let forecast_due = until_stale(
+        shown.forecast.as_ref().map(|f| f.fetched_at),
+        forecast_every,
+    );
+    let radar_due = until_stale(shown.radar.as_ref().map(|r| r.fetched_at), radar_every);
+
+    let state = AppState {
+        places,
+        current,
+        picker: None,
+        // several places matched --place: the best is shown, the rest offered
+        search: cli
+            .place
+            .as_deref()
+            .filter(|_| !others.is_empty())
+            .map(|query| Search::with_results(query, others)),
+        mono: cli.mono,
+        tab: Tab::Now,
+        units,
+        extra_columns: false,
+        radar_zoom,
+        radar_pos: 0,
+        radar_playing: true,
+        radar_hold: 0,
+        radar_center: None,
+        radar_cursor: (0, 0),
+        radar_pan_gen: 0,
+        forecast_every,
+        radar_every,
+        alert_rules: config.alerts,
+        cache,
+        provider,
+        geocoder,
+    };
+
     // Start background refresh tasks
     let shared = RwLock::new(state);
 
     let (tx, mut rx) = mpsc::channel::<Cmd>(16);
 
-    spawn_forecast_refresher(
-        tx.clone(),
-        Duration::from_secs(cli.forecast_refresh_min * 60),
-    );
-    spawn_radar_refresher(tx.clone(), Duration::from_secs(cli.radar_refresh_min * 60));
+    spawn_forecast_refresher(tx.clone(), forecast_every, forecast_due);
+    spawn_radar_refresher(tx.clone(), radar_every, radar_due);
 
     // TUI setup
     let mut terminal = setup_terminal()?;
     let mut last_tick = Instant::now();
+    let mut last_radar_frame = Instant::now();
 
     loop {
         // Drain commands coming from background refreshers and manual refreshes
         while let Ok(cmd) = rx.try_recv() {
-            handle_cmd(&shared, cmd).await;
+            handle_cmd(&shared, &tx, cmd).await;
         }
 
         // Render
@@ -208,10 +582,10 @@ async fn main() -> Result<()> {
         let timeout = Duration::from_millis(33);
         if event::poll(timeout)? {
             if let Event::Key(k) = event::read()? {
-                if k.kind == KeyEventKind::Press {
-                    if handle_key(&tx, &shared, &snapshot, k.code).await? {
-                        break;
-                    }
+                if k.kind == KeyEventKind::Press
+                    && handle_key(&tx, &shared, &snapshot, k.code, k.modifiers).await?
+                {
+                    break;
                 }
             }
         }
@@ -220,6 +594,11 @@ async fn main() -> Result<()> {
         if last_tick.elapsed() >= Duration::from_millis(250) {
             last_tick = Instant::now();
         }
+
+        if last_radar_frame.elapsed() >= RADAR_FRAME_EVERY {
+            last_radar_frame = Instant::now();
+            handle_cmd(&shared, &tx, Cmd::AdvanceRadarLoop).await;
+        }
     }
 
     restore_terminal(&mut terminal)?;
@@ -230,37 +609,88 @@ async fn main() -> Result<()> {
 enum Cmd {
     RefreshForecast,
     RefreshRadar,
+    // results of the fetches started by the two above, for the place at that index (and
+    // which of its radar fetches it was)
+    ForecastFetched(usize, Result<ForecastData>),
+    RadarFetched(usize, u64, Result<RadarData>),
     SetTab(Tab),
+    CycleLocation(i8),
+    SelectLocation(usize),
+    OpenPicker,
+    MovePicker(i8),
+    ClosePicker,
+    OpenSearch,
+    // a typed character, or None for backspace
+    EditSearch(Option<char>),
+    SubmitSearch,
+    // the query searched for and what was found
+    SearchDone(String, Result<Vec<Candidate>>),
+    MoveSearch(i8),
+    PickSearchResult(usize),
+    CloseSearch,
+    AdvanceRadarLoop,
+    ToggleRadarLoop,
+    // both pause the loop
+    StepRadar(i8),
+    SeekRadar(usize),
+    // in quarter views, and in cells
+    PanRadar(i8, i8),
+    PanSettled(u64),
+    MoveCrosshair(i8, i8),
+    RecenterRadar,
     AdjustRadarZoom(i8),
     ClearError,
     ToggleTempUnit,
+    ToggleUnitSystem,
+    ToggleExtraColumns,
 }
 
 impl AppState {
-    fn clone_for_render(&self) -> RenderState {
-        RenderState {
-            location: self.location.clone(),
-            mono: self.mono,
-            tab: self.tab,
-            temp_unit: self.temp_unit,
-            forecast: self.forecast.clone(),
-            radar: self.radar.clone(),
-            last_error: self.last_error.clone(),
+    // Where radar for the place at `i` should be centered.
+    fn radar_center(&self, i: usize) -> (f64, f64) {
+        let loc = &self.places[i].location;
+        match self.radar_center {
+            Some(c) if i == self.current => c,
+            _ => (loc.lat, loc.lon),
         }
     }
-}
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TempUnit {
-    C,
-    F,
-}
+    fn radar_frames(&self) -> usize {
+        self.places[self.current]
+            .radar
+            .as_ref()
+            .map_or(0, |rd| rd.frames.len())
+    }
 
-impl TempUnit {
-    fn toggle(self) -> Self {
-        match self {
-            TempUnit::C => TempUnit::F,
-            TempUnit::F => TempUnit::C,
+    fn clone_for_render(&self) -> RenderState {
+        let now = Local::now();
+        let place = &self.places[self.current];
+        RenderState {
+            location: place.location.clone(),
+            places: self.places.iter().map(|p| p.location.clone()).collect(),
+            current: self.current,
+            picker: self.picker,
+            search: self.search.clone(),
+            mono: self.mono,
+            tab: self.tab,
+            units: self.units,
+            extra_columns: self.extra_columns,
+            forecast: place.forecast.clone(),
+            radar: place.radar.clone(),
+            radar_pos: self.radar_pos,
+            radar_playing: self.radar_playing,
+            radar_center: self.radar_center(self.current),
+            radar_cursor: self.radar_cursor,
+            forecast_every: self.forecast_every,
+            radar_every: self.radar_every,
+            forecast_loading: place.forecast_loading,
+            radar_loading: place.radar_loading,
+            forecast_error: place.forecast_error.clone(),
+            radar_error: place.radar_error.clone(),
+            forecast_source: self.provider.describe(),
+            alert_rules: self.alert_rules.clone(),
+            alerts: place.alerts.clone(),
+            now,
         }
     }
 }
@@ -268,12 +698,31 @@ impl TempUnit {
 #[derive(Debug, Clone)]
 struct RenderState {
     location: Location,
+    places: Vec<Location>,
+    current: usize,
+    picker: Option<usize>,
+    search: Option<Search>,
     mono: bool,
     tab: Tab,
-    temp_unit: TempUnit,
+    units: Units,
+    extra_columns: bool,
     forecast: Option<ForecastData>,
-    radar: Option<RadarData>,
-    last_error: Option<String>,
+    radar: Option<Arc<RadarData>>,
+    radar_pos: usize,
+    radar_playing: bool,
+    radar_center: (f64, f64),
+    radar_cursor: (i16, i16),
+    forecast_every: Duration,
+    radar_every: Duration,
+    forecast_loading: bool,
+    radar_loading: bool,
+    forecast_error: Option<String>,
+    radar_error: Option<String>,
+    forecast_source: String,
+    alert_rules: Vec<AlertRule>,
+    alerts: Vec<Alert>,
+    // when the snapshot was taken; the charts' "now"
+    now: DateTime<Local>,
 }
 
 async fn handle_key(
@@ -281,16 +730,78 @@ async fn handle_key(
     shared: &RwLock<AppState>,
     snap: &RenderState,
     code: KeyCode,
+    mods: KeyModifiers,
 ) -> Result<bool> {
+    // the search box takes every key while it is open, so letters can be typed
+    if let Some(search) = &snap.search {
+        let cmd = match code {
+            KeyCode::Esc => Some(Cmd::CloseSearch),
+            KeyCode::Enter if search.picking() => Some(Cmd::PickSearchResult(search.sel)),
+            KeyCode::Enter => Some(Cmd::SubmitSearch),
+            KeyCode::Up => Some(Cmd::MoveSearch(-1)),
+            KeyCode::Down => Some(Cmd::MoveSearch(1)),
+            KeyCode::Backspace => Some(Cmd::EditSearch(None)),
+            KeyCode::Char(c) if !mods.contains(KeyModifiers::CONTROL) => {
+                Some(Cmd::EditSearch(Some(c)))
+            }
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            tx.send(cmd).await.ok();
+        }
+        return Ok(false);
+    }
+
+    // the location picker takes the keys while it is open
+    if let Some(sel) = snap.picker {
+        match code {
+            KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(true),
+            KeyCode::Up => {
+                tx.send(Cmd::MovePicker(-1)).await.ok();
+            }
+            KeyCode::Down => {
+                tx.send(Cmd::MovePicker(1)).await.ok();
+            }
+            KeyCode::Enter => {
+                tx.send(Cmd::SelectLocation(sel)).await.ok();
+            }
+            KeyCode::Esc | KeyCode::Char('p') | KeyCode::Char('P') => {
+                tx.send(Cmd::ClosePicker).await.ok();
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // on the radar the arrows pan the map, or move the crosshair with Shift
+    if snap.tab == Tab::Radar {
+        let step = match code {
+            KeyCode::Left => Some((-1, 0)),
+            KeyCode::Right => Some((1, 0)),
+            KeyCode::Up => Some((0, -1)),
+            KeyCode::Down => Some((0, 1)),
+            _ => None,
+        };
+        if let Some((dx, dy)) = step {
+            let cmd = if mods.contains(KeyModifiers::SHIFT) {
+                Cmd::MoveCrosshair(dx, dy)
+            } else {
+                Cmd::PanRadar(dx, dy)
+            };
+            tx.send(cmd).await.ok();
+            return Ok(false);
+        }
+    }
+
     match code {
         KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(true),
-        KeyCode::Left => {
+        KeyCode::Left | KeyCode::BackTab => {
             let tabs = Tab::all();
             let i = tabs.iter().position(|t| *t == snap.tab).unwrap_or(0);
             let ni = if i == 0 { tabs.len() - 1 } else { i - 1 };
             tx.send(Cmd::SetTab(tabs[ni])).await.ok();
         }
-        KeyCode::Right => {
+        KeyCode::Right | KeyCode::Tab => {
             let tabs = Tab::all();
             let i = tabs.iter().position(|t| *t == snap.tab).unwrap_or(0);
             let ni = (i + 1) % tabs.len();
@@ -313,6 +824,44 @@ async fn handle_key(
         KeyCode::Char('f') | KeyCode::Char('F') => {
             tx.send(Cmd::ToggleTempUnit).await.ok();
         }
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            tx.send(Cmd::ToggleUnitSystem).await.ok();
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') => {
+            tx.send(Cmd::ToggleExtraColumns).await.ok();
+        }
+        KeyCode::Char('l') => {
+            tx.send(Cmd::CycleLocation(1)).await.ok();
+        }
+        KeyCode::Char('L') => {
+            tx.send(Cmd::CycleLocation(-1)).await.ok();
+        }
+        KeyCode::Char('p') | KeyCode::Char('P') => {
+            tx.send(Cmd::OpenPicker).await.ok();
+        }
+        KeyCode::Char('/') => {
+            tx.send(Cmd::OpenSearch).await.ok();
+        }
+        KeyCode::Char(' ') => {
+            tx.send(Cmd::ToggleRadarLoop).await.ok();
+        }
+        KeyCode::Char('h') if snap.tab == Tab::Radar => {
+            tx.send(Cmd::RecenterRadar).await.ok();
+        }
+        KeyCode::Char(',') => {
+            tx.send(Cmd::StepRadar(-1)).await.ok();
+        }
+        KeyCode::Char('.') => {
+            tx.send(Cmd::StepRadar(1)).await.ok();
+        }
+        KeyCode::Home => {
+            tx.send(Cmd::SeekRadar(0)).await.ok();
+        }
+        KeyCode::End => {
+            // the newest observed frame, not the end of the nowcast
+            let latest = snap.radar.as_ref().map_or(0, |rd| rd.latest_past());
+            tx.send(Cmd::SeekRadar(latest)).await.ok();
+        }
         _ => {}
     }
 
@@ -328,7 +877,7 @@ async fn handle_key(
     Ok(false)
 }
 
-async fn handle_cmd(shared: &RwLock<AppState>, cmd: Cmd) {
+async fn handle_cmd(shared: &RwLock<AppState>, tx: &mpsc::Sender<Cmd>, cmd: Cmd) {
     match cmd {
         Cmd::SetTab(t) => {
             let mut st = shared.write().await;
@@ -336,78 +885,391 @@ async fn handle_cmd(shared: &RwLock<AppState>, cmd: Cmd) {
         }
         Cmd::ClearError => {
             let mut st = shared.write().await;
-            st.last_error = None;
+            let cur = st.current;
+            st.places[cur].forecast_error = None;
+            st.places[cur].radar_error = None;
         }
         Cmd::ToggleTempUnit => {
             let mut st = shared.write().await;
-            st.temp_unit = st.temp_unit.toggle();
+            st.units.temp = st.units.temp.toggle();
+        }
+        Cmd::ToggleUnitSystem => {
+            let mut st = shared.write().await;
+            st.units = st.units.toggle_system();
+        }
+        Cmd::ToggleExtraColumns => {
+            let mut st = shared.write().await;
+            st.extra_columns = !st.extra_columns;
         }
         Cmd::AdjustRadarZoom(delta) => {
-            let (lat, lon, new_zoom) = {
-                let mut st = shared.write().await;
-                let z = st
-                    .radar
-                    .as_ref()
-                    .map(|r| r.zoom)
-                    .unwrap_or(6)
-                    .clamp(1, 10);
-                let nz = (z as i16 + delta as i16).clamp(1, 10) as u8;
-                st.last_error = None;
-                (st.location.lat, st.location.lon, nz)
+            let mut st = shared.write().await;
+            let nz = (st.radar_zoom as i16 + delta as i16).clamp(1, 10) as u8;
+            if nz == st.radar_zoom {
+                return;
+            }
+            st.radar_zoom = nz;
+            let cur = st.current;
+            show_cached_radar(&mut st, cur);
+            start_radar_fetch(&mut st, cur, tx);
+        }
+        Cmd::CycleLocation(delta) => {
+            let mut st = shared.write().await;
+            let n = st.places.len() as i32;
+            let next = (st.current as i32 + delta as i32).rem_euclid(n) as usize;
+            switch_location(&mut st, next, tx);
+        }
+        Cmd::SelectLocation(i) => {
+            let mut st = shared.write().await;
+            st.picker = None;
+            if i < st.places.len() {
+                switch_location(&mut st, i, tx);
+            }
+        }
+        Cmd::OpenPicker => {
+            let mut st = shared.write().await;
+            st.picker = Some(st.current);
+        }
+        Cmd::MovePicker(delta) => {
+            let mut st = shared.write().await;
+            let last = st.places.len() as i32 - 1;
+            if let Some(sel) = st.picker {
+                st.picker = Some((sel as i32 + delta as i32).clamp(0, last) as usize);
+            }
+        }
+        Cmd::ClosePicker => {
+            let mut st = shared.write().await;
+            st.picker = None;
+        }
+        Cmd::OpenSearch => {
+            let mut st = shared.write().await;
+            st.picker = None;
+            st.search = Some(Search::default());
+        }
+        Cmd::EditSearch(c) => {
+            let mut st = shared.write().await;
+            if let Some(search) = st.search.as_mut() {
+                match c {
+                    Some(c) => search.query.push(c),
+                    None => {
+                        search.query.pop();
+                    }
+                }
+                search.error = None;
+            }
+        }
+        Cmd::SubmitSearch => {
+            let mut st = shared.write().await;
+            start_place_search(&mut st, tx);
+        }
+        Cmd::SearchDone(query, res) => {
+            let mut st = shared.write().await;
+            // closed in the meantime
+            let Some(search) = st.search.as_mut() else {
+                return;
             };
-
-            match fetch_radar(lat, lon, new_zoom).await {
-                Ok(rd) => {
-                    let mut st = shared.write().await;
-                    st.radar = Some(rd);
+            search.searching = false;
+            search.sel = 0;
+            search.searched = query.clone();
+            match res {
+                Ok(found) if found.is_empty() => {
+                    search.results = found;
+                    search.error = Some(format!("no place matches '{query}'"));
                 }
+                Ok(found) => search.results = found,
                 Err(e) => {
-                    let mut st = shared.write().await;
-                    st.last_error = Some(format!("radar: {e:#}"));
+                    search.results.clear();
+                    search.error = Some(format!("{e:#}"));
                 }
             }
         }
-        Cmd::RefreshForecast => {
-            let (lat, lon) = {
-                let st = shared.read().await;
-                (st.location.lat, st.location.lon)
+        Cmd::MoveSearch(delta) => {
+            let mut st = shared.write().await;
+            if let Some(search) = st.search.as_mut() {
+                let last = search.results.len().saturating_sub(1) as i32;
+                search.sel = (search.sel as i32 + delta as i32).clamp(0, last) as usize;
+            }
+        }
+        Cmd::PickSearchResult(i) => {
+            let mut st = shared.write().await;
+            let Some(found) = st.search.take().and_then(|s| s.results.into_iter().nth(i)) else {
+                return;
             };
-            match fetch_forecast(lat, lon).await {
-                Ok(fc) => {
-                    let mut st = shared.write().await;
-                    st.forecast = Some(fc);
-                    st.last_error = None;
-                }
-                Err(e) => {
-                    let mut st = shared.write().await;
-                    st.last_error = Some(format!("forecast: {e:#}"));
+            let loc = found.location();
+            // a place already in the list is switched to, not added twice
+            let i = match st
+                .places
+                .iter()
+                .position(|p| config::same_place(&p.location, &loc))
+            {
+                Some(i) => i,
+                None => {
+                    let place = Place::new(loc, st.cache.as_ref(), st.radar_zoom, &st.alert_rules);
+                    st.places.push(place);
+                    st.places.len() - 1
                 }
+            };
+            switch_location(&mut st, i, tx);
+        }
+        Cmd::CloseSearch => {
+            let mut st = shared.write().await;
+            st.search = None;
+        }
+        Cmd::AdvanceRadarLoop => {
+            let mut st = shared.write().await;
+            let n = st.radar_frames();
+            if !st.radar_playing || st.tab != Tab::Radar || n == 0 {
+                return;
+            }
+            if st.radar_hold > 0 {
+                st.radar_hold -= 1;
+                return;
+            }
+            st.radar_pos = (st.radar_pos + 1) % n;
+            if st.radar_pos == n - 1 {
+                st.radar_hold = RADAR_LOOP_HOLD;
+            }
+        }
+        Cmd::ToggleRadarLoop => {
+            let mut st = shared.write().await;
+            st.radar_playing = !st.radar_playing;
+            st.radar_hold = 0;
+        }
+        Cmd::StepRadar(delta) => {
+            let mut st = shared.write().await;
+            let n = st.radar_frames() as i32;
+            st.radar_playing = false;
+            if n > 0 {
+                let pos = st.radar_pos.min(n as usize - 1) as i32;
+                st.radar_pos = (pos + delta as i32).rem_euclid(n) as usize;
+            }
+        }
+        Cmd::SeekRadar(pos) => {
+            let mut st = shared.write().await;
+            st.radar_playing = false;
+            st.radar_pos = pos.min(st.radar_frames().saturating_sub(1));
+        }
+        Cmd::PanRadar(dx, dy) => {
+            let mut st = shared.write().await;
+            let (lat, lon) = st.radar_center(st.current);
+            let (x, y) = lat_lon_to_world_px(lat, lon, st.radar_zoom, TILE_PX);
+            st.radar_center = Some(world_px_to_lat_lon(
+                x + dx as f64 * RADAR_PAN_PX,
+                y + dy as f64 * RADAR_PAN_PX,
+                st.radar_zoom,
+                TILE_PX,
+            ));
+            // what is already on screen shifts at once; fetch when the panning stops
+            st.radar_pan_gen += 1;
+            let (generation, tx) = (st.radar_pan_gen, tx.clone());
+            tokio::spawn(async move {
+                tokio::time::sleep(RADAR_PAN_SETTLE).await;
+                tx.send(Cmd::PanSettled(generation)).await.ok();
+            });
+        }
+        Cmd::PanSettled(generation) => {
+            let mut st = shared.write().await;
+            if generation == st.radar_pan_gen {
+                let cur = st.current;
+                start_radar_fetch(&mut st, cur, tx);
+            }
+        }
+        Cmd::MoveCrosshair(dx, dy) => {
+            let mut st = shared.write().await;
+            let (half_w, half_h) = (RADAR_COLS as i16 / 2, RADAR_ROWS as i16 / 2);
+            let (cx, cy) = st.radar_cursor;
+            st.radar_cursor = (
+                (cx + dx as i16).clamp(-half_w, half_w - 1),
+                (cy + dy as i16).clamp(-half_h, half_h - 1),
+            );
+        }
+        Cmd::RecenterRadar => {
+            let mut st = shared.write().await;
+            st.radar_cursor = (0, 0);
+            if st.radar_center.take().is_some() {
+                st.radar_pan_gen += 1;
+                let cur = st.current;
+                show_cached_radar(&mut st, cur);
+                start_radar_fetch(&mut st, cur, tx);
+            }
+        }
+        Cmd::RefreshForecast => {
+            let mut st = shared.write().await;
+            let cur = st.current;
+            if !st.places[cur].forecast_loading {
+                start_forecast_fetch(&mut st, cur, tx);
             }
         }
         Cmd::RefreshRadar => {
-            let (lat, lon, zoom) = {
-                let st = shared.read().await;
-                let z = st.radar.as_ref().map(|r| r.zoom).unwrap_or(6);
-                (st.location.lat, st.location.lon, z)
-            };
-            match fetch_radar(lat, lon, zoom).await {
-                Ok(rd) => {
-                    let mut st = shared.write().await;
-                    st.radar = Some(rd);
-                    st.last_error = None;
+            let mut st = shared.write().await;
+            let cur = st.current;
+            if !st.places[cur].radar_loading {
+                start_radar_fetch(&mut st, cur, tx);
+            }
+        }
+        Cmd::ForecastFetched(i, res) => {
+            let mut st = shared.write().await;
+            let found = res
+                .as_ref()
+                .map(|fc| alerts::evaluate(&st.alert_rules, fc, Local::now()))
+                .unwrap_or_default();
+            let place = &mut st.places[i];
+            place.forecast_loading = false;
+            match res {
+                Ok(fc) => {
+                    place.alerts = found;
+                    place.forecast = Some(fc);
+                    place.forecast_error = None;
                 }
-                Err(e) => {
-                    let mut st = shared.write().await;
-                    st.last_error = Some(format!("radar: {e:#}"));
+                // keep showing the old data; the footer marks it stale
+                Err(e) => place.forecast_error = Some(format!("forecast: {e:#}")),
+            }
+        }
+        Cmd::RadarFetched(i, generation, res) => {
+            let mut st = shared.write().await;
+            let zoom = st.radar_zoom;
+            let (lat, lon) = st.radar_center(i);
+            let place = &mut st.places[i];
+            // overtaken by a later fetch, which is still loading
+            if generation != place.radar_gen {
+                return;
+            }
+            place.radar_loading = false;
+            match res {
+                // a fetch for a zoom level or a spot we have since left
+                Ok(rd) if rd.zoom != zoom || rd.lat != lat || rd.lon != lon => {}
+                Ok(rd) => {
+                    place.radar = Some(Arc::new(rd));
+                    place.radar_error = None;
                 }
+                Err(e) => place.radar_error = Some(format!("radar: {e:#}")),
             }
         }
     }
 }
 
-fn spawn_forecast_refresher(tx: mpsc::Sender<Cmd>, every: Duration) {
+// Show another place, fetching whatever it has that is missing or stale.
+fn switch_location(st: &mut AppState, i: usize, tx: &mpsc::Sender<Cmd>) {
+    st.current = i;
+    st.radar_center = None;
+    st.radar_cursor = (0, 0);
+    st.radar_pan_gen += 1;
+    let (lat, lon) = st.radar_center(i);
+    if st.places[i]
+        .radar
+        .as_ref()
+        .is_some_and(|r| r.zoom != st.radar_zoom || r.lat != lat || r.lon != lon)
+    {
+        show_cached_radar(st, i);
+    }
+
+    let place = &st.places[i];
+    let forecast_due = until_stale(
+        place.forecast.as_ref().map(|f| f.fetched_at),
+        st.forecast_every,
+    );
+    let radar_due = until_stale(place.radar.as_ref().map(|r| r.fetched_at), st.radar_every);
+    if forecast_due.is_zero() && !place.forecast_loading {
+        start_forecast_fetch(st, i, tx);
+    }
+    let place = &st.places[i];
+    if radar_due.is_zero() && !place.radar_loading {
+        start_radar_fetch(st, i, tx);
+    }
+}
+
+// Show the cached frames for the current zoom (if any) while new ones load. Only the
+// view centered on the location itself is cached.
+fn show_cached_radar(st: &mut AppState, i: usize) {
+    if i == st.current && st.radar_center.is_some() {
+        return;
+    }
+    let place = &mut st.places[i];
+    let cached = st
+        .cache
+        .as_ref()
+        .and_then(|c| c.load_radar(&place.location, st.radar_zoom));
+    if cached.is_some() {
+        place.radar = cached.map(Arc::new);
+    }
+    place.radar_error = None;
+}
+
+fn start_forecast_fetch(st: &mut AppState, i: usize, tx: &mpsc::Sender<Cmd>) {
+    st.places[i].forecast_loading = true;
+    let (loc, cache, provider, tx) = (
+        st.places[i].location.clone(),
+        st.cache.clone(),
+        st.provider.clone(),
+        tx.clone(),
+    );
     tokio::spawn(async move {
-        let mut t = tokio::time::interval(every);
+        let res = provider.fetch(loc.lat, loc.lon).await;
+        if let (Ok(fc), Some(c)) = (&res, &cache) {
+            // best-effort; a failed write only costs an offline start
+            c.store_forecast(&loc, fc).ok();
+        }
+        tx.send(Cmd::ForecastFetched(i, res)).await.ok();
+    });
+}
+
+fn start_place_search(st: &mut AppState, tx: &mpsc::Sender<Cmd>) {
+    let Some(search) = st.search.as_mut() else {
+        return;
+    };
+    let query = search.query.trim().to_string();
+    if query.is_empty() || search.searching {
+        return;
+    }
+    search.searching = true;
+    search.error = None;
+    let (geocoder, tx) = (st.geocoder.clone(), tx.clone());
+    tokio::spawn(async move {
+        let res = geocoder.search(&query).await;
+        tx.send(Cmd::SearchDone(query, res)).await.ok();
+    });
+}
+
+fn start_radar_fetch(st: &mut AppState, i: usize, tx: &mpsc::Sender<Cmd>) {
+    let place = &mut st.places[i];
+    place.radar_loading = true;
+    place.radar_gen += 1;
+    let generation = place.radar_gen;
+    let (lat, lon) = st.radar_center(i);
+    let panned = i == st.current && st.radar_center.is_some();
+    // frames already on hand for this exact view are kept rather than downloaded again
+    let held = st.places[i]
+        .radar
+        .as_deref()
+        .filter(|r| r.zoom == st.radar_zoom && r.lat == lat && r.lon == lon)
+        .cloned();
+    let (loc, zoom, cache, tx) = (
+        st.places[i].location.clone(),
+        st.radar_zoom,
+        st.cache.clone(),
+        tx.clone(),
+    );
+    tokio::spawn(async move {
+        // tiles are cached either way; the frames only for the location itself
+        let res = fetch_radar(lat, lon, zoom, cache.as_ref(), held).await;
+        if let (Ok(rd), Some(c), false) = (&res, &cache, panned) {
+            c.store_radar(&loc, rd).ok();
+        }
+        tx.send(Cmd::RadarFetched(i, generation, res)).await.ok();
+    });
+}
+
+// How long until data fetched at `fetched_at` is due for a refresh (now, if never fetched).
+fn until_stale(fetched_at: Option<DateTime<Local>>, every: Duration) -> Duration {
+    let Some(at) = fetched_at else {
+        return Duration::ZERO;
+    };
+    let age = (Local::now() - at).to_std().unwrap_or(Duration::ZERO);
+    every.saturating_sub(age)
+}
+
+fn spawn_forecast_refresher(tx: mpsc::Sender<Cmd>, every: Duration, first: Duration) {
+    tokio::spawn(async move {
+        let mut t = tokio::time::interval_at(tokio::time::Instant::now() + first, every);
         loop {
             t.tick().await;
             tx.send(Cmd::RefreshForecast).await.ok();
@@ -415,9 +1277,9 @@ fn spawn_forecast_refresher(tx: mpsc::Sender<Cmd>, every: Duration) {
     });
 }
 
-fn spawn_radar_refresher(tx: mpsc::Sender<Cmd>, every: Duration) {
+fn spawn_radar_refresher(tx: mpsc::Sender<Cmd>, every: Duration, first: Duration) {
     tokio::spawn(async move {
-        let mut t = tokio::time::interval(every);
+        let mut t = tokio::time::interval_at(tokio::time::Instant::now() + first, every);
         loop {
             t.tick().await;
             tx.send(Cmd::RefreshRadar).await.ok();
@@ -463,28 +1325,50 @@ fn draw_frame(term: &mut Terminal<CrosstermBackend<Stdout>>, st: &RenderState) -
         let _ = execute!(io::stdout(), BeginSynchronizedUpdate);
 
         let area = f.size();
+        let mut title = vec![
+            Span::styled(" weather ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" "),
+            Span::styled(
+                format!(
+                    "{} ({:.4}, {:.4})",
+                    st.location.name, st.location.lat, st.location.lon
+                ),
+                Style::default().fg(if mono { Color::White } else { Color::Cyan }),
+            ),
+        ];
+        if st.places.len() > 1 {
+            title.push(Span::raw(format!(
+                " [{}/{}] ",
+                st.current + 1,
+                st.places.len()
+            )));
+        }
         let outer = Block::default()
             .borders(Borders::ALL)
-            .title(Line::from(vec![
-                Span::styled(" weather ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" "),
-                Span::styled(
-                    format!("{} ({:.4}, {:.4})", st.location.name, st.location.lat, st.location.lon),
-                    Style::default().fg(if mono { Color::White } else { Color::Cyan }),
-                ),
-            ]))
+            .title(Line::from(title))
             .border_style(Style::default().fg(if mono { Color::Gray } else { Color::DarkGray }));
         f.render_widget(outer, area);
 
         let inner = area.inner(Margin { horizontal: 1, vertical: 1 });
         let rows = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Min(0),
+                // a third line for the alert banner
+                Constraint::Length(if st.alerts.is_empty() { 4 } else { 5 }),
+            ])
             .split(inner);
 
         render_tabs(f, rows[0], st);
         render_main(f, rows[1], st);
         render_footer(f, rows[2], st);
+        if let Some(sel) = st.picker {
+            render_picker(f, rows[1], st, sel);
+        }
+        if let Some(search) = &st.search {
+            render_search(f, rows[1], st, search);
+        }
 
         let _ = execute!(io::stdout(), EndSynchronizedUpdate);
     })?;
@@ -512,40 +1396,243 @@ fn render_tabs(f: &mut Frame, area: Rect, st: &RenderState) {
 }
 
 fn render_footer(f: &mut Frame, area: Rect, st: &RenderState) {
-    let mut spans = vec![
+    let spans = vec![
         Span::styled("q", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(" quit  "),
-        Span::styled("←/→", Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled("Tab ←/→", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(" tabs  "),
         Span::styled("r", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(" refresh  "),
         Span::styled("+/-", Style::default().add_modifier(Modifier::BOLD)),
         Span::raw(" radar zoom  "),
-        Span::styled("f", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" C/F  "),
+        Span::styled("f/u", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" C/F, units  "),
         Span::styled("c", Style::default().add_modifier(Modifier::BOLD)),
-        Span::raw(" clear error"),
+        Span::raw(" clear error  "),
+        Span::styled("l/p", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" location  "),
+        Span::styled("/", Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(" find place"),
     ];
 
-    if let Some(e) = &st.last_error {
-        spans.push(Span::raw("   "));
-        spans.push(Span::styled(
+    // second line: how old each dataset is, and what went wrong fetching it
+    let mut status = Vec::new();
+    status.extend(data_status(
+        "Forecast",
+        st.forecast.as_ref().map(|f| f.fetched_at),
+        st.forecast_every,
+        st.forecast_loading,
+        st.mono,
+    ));
+    status.push(Span::raw("   "));
+    status.extend(data_status(
+        "Radar",
+        st.radar.as_ref().map(|r| r.fetched_at),
+        st.radar_every,
+        st.radar_loading,
+        st.mono,
+    ));
+    for e in [&st.forecast_error, &st.radar_error].into_iter().flatten() {
+        status.push(Span::raw("   "));
+        status.push(Span::styled(
             format!("ERR: {e}"),
             Style::default().fg(if st.mono { Color::White } else { Color::Red }),
         ));
     }
 
-    let p = Paragraph::new(Line::from(spans))
-        .block(Block::default().borders(Borders::ALL).title("Keys"));
+    let mut lines = vec![Line::from(spans), Line::from(status)];
+    if !st.alerts.is_empty() {
+        let list: Vec<String> = st
+            .alerts
+            .iter()
+            .map(|a| {
+                format!(
+                    "{} {} ({})",
+                    a.rule.name,
+                    st.units.day_clock(a.first),
+                    format_metric(a.rule.metric, a.worst, st.units)
+                )
+            })
+            .collect();
+        lines.push(Line::from(Span::styled(
+            format!("⚠ {}  (Alerts tab)", list.join(" · ")),
+            if st.mono {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            },
+        )));
+    }
+
+    let p = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title("Keys"));
     f.render_widget(p, area);
 }
 
+fn data_status(
+    label: &'static str,
+    fetched_at: Option<DateTime<Local>>,
+    every: Duration,
+    loading: bool,
+    mono: bool,
+) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::raw(format!("{label}: "))];
+    match fetched_at {
+        Some(at) => {
+            let age = (Local::now() - at).to_std().unwrap_or(Duration::ZERO);
+            spans.push(Span::raw(format_age(age)));
+            if age > every {
+                spans.push(Span::styled(
+                    " (stale)",
+                    Style::default()
+                        .fg(if mono { Color::White } else { Color::Yellow })
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        None => spans.push(Span::raw("none")),
+    }
+    if loading {
+        spans.push(Span::raw(" …"));
+    }
+    spans
+}
+
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h {:02}m ago", secs / 3600, secs / 60 % 60),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+// Saved locations as a popup over the main view.
+fn render_picker(f: &mut Frame, area: Rect, st: &RenderState, sel: usize) {
+    let mono = st.mono;
+    let labels: Vec<String> = st
+        .places
+        .iter()
+        .enumerate()
+        .map(|(i, loc)| {
+            let mark = if i == st.current { "•" } else { " " };
+            format!("{mark} {} ({:.4}, {:.4}) ", loc.name, loc.lat, loc.lon)
+        })
+        .collect();
+    let widest = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+
+    let w = (widest as u16 + 2).max(32).min(area.width);
+    let h = (st.places.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - w) / 2,
+        y: area.y + (area.height - h) / 2,
+        width: w,
+        height: h,
+    };
+    let list = List::new(labels)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Locations (↑/↓, Enter, Esc)"),
+        )
+        .highlight_style(if mono {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        });
+    let mut state = ListState::default().with_selected(Some(sel));
+    f.render_widget(Clear, popup);
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+// The search box, with the matches (or what became of the search) under it.
+fn render_search(f: &mut Frame, area: Rect, st: &RenderState, search: &Search) {
+    let mono = st.mono;
+    let labels: Vec<String> = search
+        .results
+        .iter()
+        .map(|c| format!(" {} ({:.4}, {:.4}) ", c.label(), c.lat, c.lon))
+        .collect();
+    let note = if search.searching {
+        Some("searching…".to_string())
+    } else if let Some(e) = &search.error {
+        Some(e.clone())
+    } else if labels.is_empty() {
+        Some("type a place, e.g. Portland, ME".to_string())
+    } else {
+        None
+    };
+    let widest = labels
+        .iter()
+        .chain(&note)
+        .map(|l| l.chars().count() + 1)
+        .max()
+        .unwrap_or(0);
+
+    let w = (widest as u16 + 2).max(40).min(area.width);
+    let list_h = if note.is_some() {
+        1
+    } else {
+        labels.len() as u16
+    };
+    let h = (list_h + 5).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - w) / 2,
+        y: area.y + (area.height - h) / 2,
+        width: w,
+        height: h,
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup);
+    f.render_widget(Clear, popup);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::raw(search.query.clone()),
+        Span::styled("█", Style::default().add_modifier(Modifier::SLOW_BLINK)),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Find a place (Enter search, Esc)"),
+    );
+    f.render_widget(input, chunks[0]);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Matches (↑/↓, Enter)");
+    match note {
+        Some(note) => {
+            let style = if search.error.is_some() && !mono {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            f.render_widget(
+                Paragraph::new(Span::styled(format!(" {note}"), style)).block(block),
+                chunks[1],
+            );
+        }
+        None => {
+            let list = List::new(labels).block(block).highlight_style(if mono {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            });
+            let mut state = ListState::default().with_selected(Some(search.sel));
+            f.render_stateful_widget(list, chunks[1], &mut state);
+        }
+    }
+}
+
 fn render_main(f: &mut Frame, area: Rect, st: &RenderState) {
     match st.tab {
         Tab::Now => render_now(f, area, st),
         Tab::Hourly => render_hourly(f, area, st),
         Tab::Daily => render_daily(f, area, st),
         Tab::Radar => render_radar(f, area, st),
+        Tab::Alerts => render_alerts(f, area, st),
         Tab::Help => render_help(f, area, st),
     }
 }
@@ -556,26 +1643,72 @@ fn render_now(f: &mut Frame, area: Rect, st: &RenderState) {
     if let Some(fc) = &st.forecast {
         let icon = code_icon(fc.current.code);
         let desc = code_desc(fc.current.code);
-        let (temp, unit) = format_temp(fc.current.temp_c, st.temp_unit);
-        let lines = vec![
+        let units = st.units;
+        let (temp, unit) = format_temp(fc.current.temp_c, units.temp);
+        let observed = parse_local_time(&fc.current.time_local)
+            .map(|t| format!("{} {}", t.format("%Y-%m-%d"), units.clock(t)))
+            .unwrap_or_else(|| fc.current.time_local.clone());
+        let mut lines = vec![
             Line::from(vec![
                 Span::styled(icon, Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(" "),
                 Span::styled(desc, Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw("   "),
-                Span::raw(fc.current.time_local.clone()),
+                Span::raw(observed),
             ]),
             Line::from(format!(
-                "Temp: {:.1}°{}   Wind: {:.0} km/h   Cloud: {:.0}%   Precip: {:.1} mm ({}%)",
+                "Temp: {:.1}°{}   Wind: {}   Cloud: {:.0}%   Precip: {} ({}%)",
                 temp,
                 unit,
-                fc.current.wind_kph,
+                units.format_wind(fc.current.wind_kph),
                 fc.current.cloud_pct,
-                fc.current.precip_mm,
+                units.format_precip(fc.current.precip_mm),
                 fc.current.precip_prob_pct.round()
             )),
-            Line::from(format!("Fetched: {}", fc.fetched_at.format("%Y-%m-%d %H:%M:%S"))),
         ];
+        let cur = &fc.current;
+        // forecasts cached before these were fetched have zeros here
+        if cur.pressure_hpa > 0.0 {
+            let (feels, _) = format_temp(cur.feels_like_c, units.temp);
+            let (dew, _) = format_temp(cur.dew_point_c, units.temp);
+            lines.push(Line::from(format!(
+                "Feels like: {feels:.1}°{unit}   Humidity: {:.0}%   Dew point: {dew:.1}°{unit}   UV: {:.1} ({})",
+                cur.humidity_pct,
+                cur.uv_index,
+                uv_desc(cur.uv_index)
+            )));
+            // the trend also needs the hourly reading from 3 h ago
+            let trend = fc
+                .pressure_trend()
+                .map(|trend| {
+                    format!(
+                        " {} ({}{} in 3 h)",
+                        pressure_desc(trend),
+                        if trend >= 0.0 { "+" } else { "" },
+                        units.format_pressure(trend)
+                    )
+                })
+                .unwrap_or_default();
+            lines.push(Line::from(format!(
+                "Wind from: {} ({:.0}°), gusts {}   Pressure: {}{trend}",
+                compass(cur.wind_dir_deg),
+                cur.wind_dir_deg,
+                units.format_wind(cur.gust_kph),
+                units.format_pressure(cur.pressure_hpa)
+            )));
+        }
+        if let Some(day) = fc.today().filter(|d| !d.sunrise_local.is_empty()) {
+            lines.push(Line::from(format!(
+                "Sunrise: {}   Sunset: {}",
+                clock_time(&day.sunrise_local, units),
+                clock_time(&day.sunset_local, units)
+            )));
+        }
+        lines.push(Line::from(format!(
+            "Fetched: {} ({})",
+            units.timestamp(fc.fetched_at),
+            fc.timezone
+        )));
 
         f.render_widget(
             Paragraph::new(lines)
@@ -597,47 +1730,101 @@ fn render_hourly(f: &mut Frame, area: Rect, st: &RenderState) {
     let mono = st.mono;
     let block = Block::default().borders(Borders::ALL).title("Hourly (next ~24h)");
     if let Some(fc) = &st.forecast {
+        // the charts need some height to be readable; short terminals get the table only
+        let chart_h = if area.height >= 20 {
+            area.height * 55 / 100
+        } else {
+            0
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(chart_h), Constraint::Min(0)])
+            .split(area);
+        if chart_h > 0 {
+            render_hourly_charts(f, chunks[0], st, fc);
+        }
+
+        // the past hours are only there for the charts
+        let from = fc
+            .local_now(st.now)
+            .and_then(|t| t.date().and_hms_opt(t.hour(), 0, 0));
         let rows: Vec<Row> = fc
             .hourly
             .iter()
+            .filter(|h| match (from, parse_local_time(&h.time_local)) {
+                (Some(from), Some(t)) => t >= from,
+                _ => true,
+            })
             .take(24)
             .map(|h| {
                 let icon = code_icon(h.code);
-                let (temp, unit) = format_temp(h.temp_c, st.temp_unit);
-                Row::new(vec![
-                    Cell::from(h.time_local.clone()),
+                let units = st.units;
+                let (temp, unit) = format_temp(h.temp_c, units.temp);
+                let time = parse_local_time(&h.time_local)
+                    .map(|t| units.day_clock(t))
+                    .unwrap_or_else(|| h.time_local.clone());
+                let mut cells = vec![
+                    Cell::from(time),
                     Cell::from(icon),
                     Cell::from(format!("{:.1}°{}", temp, unit)),
                     Cell::from(format!("{:.0}%", h.cloud_pct)),
                     Cell::from(format!("{:.0}%", h.precip_prob_pct)),
-                    Cell::from(format!("{:.1}mm", h.precip_mm)),
-                    Cell::from(format!("{:.0}km/h", h.wind_kph)),
-                ])
+                    Cell::from(units.format_precip(h.precip_mm)),
+                    Cell::from(units.format_wind(h.wind_kph)),
+                ];
+                if st.extra_columns {
+                    let (feels, _) = format_temp(h.feels_like_c, units.temp);
+                    let (dew, _) = format_temp(h.dew_point_c, units.temp);
+                    cells.extend([
+                        Cell::from(format!(
+                            "{:.0} {}",
+                            units.wind(h.gust_kph),
+                            compass(h.wind_dir_deg)
+                        )),
+                        Cell::from(format!("{:.1}°{}", feels, unit)),
+                        Cell::from(format!("{:.0}%", h.humidity_pct)),
+                        Cell::from(format!("{:.1}°{}", dew, unit)),
+                        Cell::from(match units.pressure {
+                            PressureUnit::Inhg => format!("{:.2}", units.pressure(h.pressure_hpa)),
+                            _ => format!("{:.0}", units.pressure(h.pressure_hpa)),
+                        }),
+                        Cell::from(format!("{:.1}", h.uv_index)),
+                    ]);
+                }
+                Row::new(cells)
             })
             .collect();
 
-        let header = Row::new(vec![
-            "Time", "", "Temp", "Cloud", "PoP", "Precip", "Wind",
-        ])
-        .style(Style::default().add_modifier(Modifier::BOLD));
-
-        let t = Table::new(
-            rows,
-            [
-                Constraint::Length(17),
-                Constraint::Length(2),
-                Constraint::Length(8),
-                Constraint::Length(7),
+        let mut titles = vec!["Time", "", "Temp", "Cloud", "PoP", "Precip", "Wind"];
+        let mut widths = vec![
+            Constraint::Length(12),
+            Constraint::Length(2),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(6),
+            Constraint::Length(9),
+            Constraint::Length(9),
+        ];
+        if st.extra_columns {
+            let pressure = st.units.pressure.label();
+            titles.extend(["Gust", "Feels", "Hum", "Dew", pressure, "UV"]);
+            widths.extend([
                 Constraint::Length(6),
-                Constraint::Length(9),
-                Constraint::Length(9),
-            ],
-        )
-        .header(header)
-        .block(block)
-        .column_spacing(1);
+                Constraint::Length(8),
+                Constraint::Length(4),
+                Constraint::Length(8),
+                Constraint::Length(5),
+                Constraint::Length(4),
+            ]);
+        }
+        let header = Row::new(titles).style(Style::default().add_modifier(Modifier::BOLD));
+
+        let t = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .column_spacing(1);
 
-        f.render_widget(t, area);
+        f.render_widget(t, chunks[1]);
     } else {
         f.render_widget(
             Paragraph::new("No forecast loaded yet (press r).")
@@ -652,46 +1839,74 @@ fn render_daily(f: &mut Frame, area: Rect, st: &RenderState) {
     let mono = st.mono;
     let block = Block::default().borders(Borders::ALL).title("Daily (10 days)");
     if let Some(fc) = &st.forecast {
+        // the table needs 13 rows; the chart gets the rest if that is enough to read
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(13)])
+            .split(area);
+        if chunks[0].height >= 8 {
+            render_daily_chart(f, chunks[0], st, fc);
+        }
+
         let rows: Vec<Row> = fc
             .daily
             .iter()
             .take(10)
             .map(|d| {
                 let icon = code_icon(d.code);
-                let (tmax, unit) = format_temp(d.tmax_c, st.temp_unit);
-                let (tmin, _) = format_temp(d.tmin_c, st.temp_unit);
-                Row::new(vec![
+                let (tmax, unit) = format_temp(d.tmax_c, st.units.temp);
+                let (tmin, _) = format_temp(d.tmin_c, st.units.temp);
+                let mut cells = vec![
                     Cell::from(d.date.format("%a %Y-%m-%d").to_string()),
                     Cell::from(icon),
                     Cell::from(format!("{:.1}°{}", tmax, unit)),
                     Cell::from(format!("{:.1}°{}", tmin, unit)),
                     Cell::from(format!("{:.0}%", d.precip_prob_max_pct)),
-                    Cell::from(code_desc(d.code)),
-                ])
+                ];
+                if st.extra_columns {
+                    cells.extend([
+                        Cell::from(clock_time(&d.sunrise_local, st.units)),
+                        Cell::from(clock_time(&d.sunset_local, st.units)),
+                        Cell::from(format!("{:.1}", d.uv_max)),
+                        Cell::from(format!(
+                            "{:.0} {}",
+                            st.units.wind(d.gust_max_kph),
+                            compass(d.wind_dir_deg)
+                        )),
+                    ]);
+                }
+                cells.push(Cell::from(code_desc(d.code)));
+                Row::new(cells)
             })
             .collect();
 
-        let header = Row::new(vec![
-            "Date", "", "Max", "Min", "PoP", "Summary",
-        ])
-        .style(Style::default().add_modifier(Modifier::BOLD));
-
-        let t = Table::new(
-            rows,
-            [
-                Constraint::Length(14),
-                Constraint::Length(2),
-                Constraint::Length(7),
+        let mut titles = vec!["Date", "", "Max", "Min", "PoP"];
+        let mut widths = vec![
+            Constraint::Length(14),
+            Constraint::Length(2),
+            Constraint::Length(7),
+            Constraint::Length(7),
+            Constraint::Length(6),
+        ];
+        if st.extra_columns {
+            titles.extend(["Sunrise", "Sunset", "UV", "Gust"]);
+            widths.extend([
                 Constraint::Length(7),
                 Constraint::Length(6),
-                Constraint::Min(10),
-            ],
-        )
-        .header(header)
-        .block(block)
-        .column_spacing(1);
+                Constraint::Length(4),
+                Constraint::Length(6),
+            ]);
+        }
+        titles.push("Summary");
+        widths.push(Constraint::Min(10));
+        let header = Row::new(titles).style(Style::default().add_modifier(Modifier::BOLD));
 
-        f.render_widget(t, area);
+        let t = Table::new(rows, widths)
+            .header(header)
+            .block(block)
+            .column_spacing(1);
+
+        f.render_widget(t, chunks[1]);
     } else {
         f.render_widget(
             Paragraph::new("No forecast loaded yet (press r).")
@@ -702,23 +1917,299 @@ fn render_daily(f: &mut Frame, area: Rect, st: &RenderState) {
     }
 }
 
+// Temperature, wind, rain chance and rain amount from CHART_PAST_H before now to
+// CHART_AHEAD_H after, in a 2x2 grid. x is hours from now.
+fn render_hourly_charts(f: &mut Frame, area: Rect, st: &RenderState, fc: &ForecastData) {
+    let Some(here_now) = fc.local_now(st.now) else {
+        return;
+    };
+    let series = |value: &dyn Fn(&HourRow) -> f64| -> Vec<(f64, f64)> {
+        fc.hourly
+            .iter()
+            .filter_map(|h| {
+                let x = (parse_local_time(&h.time_local)? - here_now).num_minutes() as f64 / 60.0;
+                (-CHART_PAST_H..=CHART_AHEAD_H)
+                    .contains(&x)
+                    .then(|| (x, value(h)))
+            })
+            .collect()
+    };
+    let units = st.units;
+    let temp = series(&|h| format_temp(h.temp_c, units.temp).0);
+    let wind = series(&|h| units.wind(h.wind_kph));
+    let pop = series(&|h| h.precip_prob_pct);
+    // one spike per hour, so the amounts read as bars
+    let precip: Vec<(f64, f64)> = series(&|h| units.precip(h.precip_mm))
+        .into_iter()
+        .flat_map(|(x, v)| [(x, 0.0), (x, v), (x, 0.0)])
+        .collect();
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let cells: Vec<Rect> = rows
+        .iter()
+        .flat_map(|row| {
+            Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(*row)
+                .to_vec()
+        })
+        .collect();
+
+    let (_, unit) = format_temp(0.0, units.temp);
+    let (lo, hi) = value_range(&temp);
+    let (precip_step, precip_decimals) = match units.precip {
+        PrecipUnit::Mm => (1.0, 1),
+        PrecipUnit::In => (0.1, 2),
+    };
+    let charts = [
+        (
+            format!("Temp °{unit}"),
+            &temp,
+            [(lo - 1.0).floor(), (hi + 1.0).ceil()],
+            0,
+            Color::LightRed,
+        ),
+        (
+            format!("Wind {}", units.wind.label()),
+            &wind,
+            [0.0, nice_max(value_range(&wind).1, 10.0)],
+            0,
+            Color::LightCyan,
+        ),
+        (
+            "Rain chance %".to_string(),
+            &pop,
+            [0.0, 100.0],
+            0,
+            Color::LightBlue,
+        ),
+        (
+            format!("Rain {}", units.precip.label()),
+            &precip,
+            [0.0, nice_max(value_range(&precip).1, precip_step)],
+            precip_decimals,
+            Color::Blue,
+        ),
+    ];
+
+    for ((title, data, y, decimals, color), cell) in charts.into_iter().zip(cells) {
+        let (x_bounds, x_labels) = hour_axis(cell.width);
+        let now_line = [(0.0, y[0]), (0.0, y[1])];
+        let chart = Chart::new(vec![
+            now_dataset(&now_line, st.mono),
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(chart_style(color, st.mono))
+                .data(data),
+        ])
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .x_axis(Axis::default().bounds(x_bounds).labels(x_labels))
+        .y_axis(y_axis(y, decimals));
+        f.render_widget(chart, cell);
+    }
+}
+
+// Each day's min to max as a filled band, with the max and min drawn over it. x is days
+// from the first day's midnight.
+fn render_daily_chart(f: &mut Frame, area: Rect, st: &RenderState, fc: &ForecastData) {
+    let days = fc.daily.len().min(10);
+    let Some(first) = fc.daily.first().map(|d| d.date) else {
+        return;
+    };
+    let temp = |c| format_temp(c, st.units.temp).0;
+    // the day's numbers sit at its noon
+    let maxs: Vec<(f64, f64)> = fc.daily[..days]
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (i as f64 + 0.5, temp(d.tmax_c)))
+        .collect();
+    let mins: Vec<(f64, f64)> = fc.daily[..days]
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (i as f64 + 0.5, temp(d.tmin_c)))
+        .collect();
+
+    // zigzag between the two lines, finer than a braille dot, to fill the band
+    let steps = (area.width as usize * 2).max(1);
+    let band: Vec<(f64, f64)> = (0..=steps)
+        .flat_map(|k| {
+            let x = 0.5 + (days as f64 - 1.0) * k as f64 / steps as f64;
+            [(x, interpolate(&mins, x)), (x, interpolate(&maxs, x))]
+        })
+        .collect();
+
+    let now_x = fc
+        .local_now(st.now)
+        .map(|t| (t - first.and_hms_opt(0, 0, 0).unwrap_or_default()).num_minutes() as f64)
+        .map(|m| m / (24.0 * 60.0))
+        .unwrap_or(0.0);
+
+    let (lo, _) = value_range(&mins);
+    let (_, hi) = value_range(&maxs);
+    let y = [(lo - 1.0).floor(), (hi + 1.0).ceil()];
+    let now_line = [(now_x, y[0]), (now_x, y[1])];
+
+    // a label per day when there is room, else every other day
+    let per_label = if area.width as usize >= days * 8 {
+        1
+    } else {
+        2
+    };
+    let x_days = days.div_ceil(per_label) * per_label;
+    let x_labels: Vec<Span> = (0..=x_days)
+        .step_by(per_label)
+        .map(|i| {
+            let d = first + chrono::Duration::days(i as i64);
+            Span::raw(d.format("%a %d").to_string())
+        })
+        .collect();
+
+    let (_, unit) = format_temp(0.0, st.units.temp);
+    let chart = Chart::new(vec![
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(if st.mono {
+                Style::default().add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            })
+            .data(&band),
+        now_dataset(&now_line, st.mono),
+        Dataset::default()
+            .name("max")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(chart_style(Color::LightRed, st.mono))
+            .data(&maxs),
+        Dataset::default()
+            .name("min")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(chart_style(Color::LightBlue, st.mono))
+            .data(&mins),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Min / max °{unit}")),
+    )
+    .x_axis(
+        Axis::default()
+            .bounds([0.0, x_days as f64])
+            .labels(x_labels),
+    )
+    .y_axis(y_axis(y, 0));
+    f.render_widget(chart, area);
+}
+
+fn chart_style(color: Color, mono: bool) -> Style {
+    if mono {
+        Style::default().fg(Color::White)
+    } else {
+        Style::default().fg(color)
+    }
+}
+
+// The "now" marker: a vertical line from the bottom to the top of the chart.
+fn now_dataset(line: &[(f64, f64); 2], mono: bool) -> Dataset<'_> {
+    Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(if mono {
+            Style::default().add_modifier(Modifier::DIM)
+        } else {
+            Style::default().fg(Color::Yellow)
+        })
+        .data(line)
+}
+
+// Hours from now, labelled "-12h", "now", "+12h", ... when there is room.
+fn hour_axis(width: u16) -> ([f64; 2], Vec<Span<'static>>) {
+    let n = match width {
+        48.. => 6,
+        30.. => 3,
+        _ => 2,
+    };
+    let span = CHART_PAST_H + CHART_AHEAD_H;
+    let labels = (0..n)
+        .map(|k| {
+            let h = -CHART_PAST_H + span * k as f64 / (n - 1) as f64;
+            Span::raw(if h == 0.0 {
+                "now".to_string()
+            } else {
+                format!("{h:+.0}h")
+            })
+        })
+        .collect();
+    ([-CHART_PAST_H, CHART_AHEAD_H], labels)
+}
+
+fn y_axis(bounds: [f64; 2], decimals: usize) -> Axis<'static> {
+    Axis::default().bounds(bounds).labels(
+        bounds
+            .iter()
+            .map(|v| Span::raw(format!("{v:.decimals$}")))
+            .collect(),
+    )
+}
+
+fn value_range(data: &[(f64, f64)]) -> (f64, f64) {
+    let lo = data.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let hi = data.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    if lo.is_finite() {
+        (lo, hi)
+    } else {
+        (0.0, 0.0)
+    }
+}
+
+// Round up to a multiple of `step` (and at least one step), for a y-axis top.
+fn nice_max(v: f64, step: f64) -> f64 {
+    ((v / step).ceil() * step).max(step)
+}
+
+// y at x on the line through `points` (sorted by x), held flat past either end.
+fn interpolate(points: &[(f64, f64)], x: f64) -> f64 {
+    match points.iter().position(|p| p.0 >= x) {
+        Some(0) => points[0].1,
+        Some(i) => {
+            let (a, b) = (points[i - 1], points[i]);
+            a.1 + (b.1 - a.1) * (x - a.0) / (b.0 - a.0)
+        }
+        None => points.last().map_or(0.0, |p| p.1),
+    }
+}
+
 fn render_radar(f: &mut Frame, area: Rect, st: &RenderState) {
     let mono = st.mono;
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .constraints([Constraint::Min(0), Constraint::Length(5)])
         .split(area);
 
-    let block = Block::default().borders(Borders::ALL).title("Radar (Braille)");
-    if let Some(rd) = &st.radar {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Radar (arrows pan, Shift+arrows crosshair, h back home)");
+    let info_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Loop (space play/pause, ,/. step, Home/End oldest/newest)");
+    if let Some(rd) = st.radar.as_ref().filter(|rd| !rd.frames.is_empty()) {
+        let frame = st.radar_pos.min(rd.frames.len() - 1);
+        let (view, under) = radar_view(rd, frame, &st.location, st.radar_center, st.radar_cursor);
         let inner = block.inner(chunks[0]);
         let target_w = inner.width as usize;
         let target_h = inner.height as usize;
         let lines = if target_w == 0 || target_h == 0 {
             Vec::new()
         } else {
-            let fitted = fit_radar_cells(&rd.cells, target_w, target_h);
+            let fitted = fit_radar_cells(&view, target_w, target_h);
             radar_lines(&fitted, mono)
         };
         let p = Paragraph::new(lines)
@@ -727,16 +2218,18 @@ fn render_radar(f: &mut Frame, area: Rect, st: &RenderState) {
         f.render_widget(p, chunks[0]);
 
         let info = format!(
-            "{} | frame UTC: {} | zoom: {} | fetched: {}",
+            "{} | zoom: {} | fetched: {}",
             rd.info,
-            rd.frame_time_utc,
             rd.zoom,
-            rd.fetched_at.format("%Y-%m-%d %H:%M:%S")
+            st.units.timestamp(rd.fetched_at)
         );
         f.render_widget(
-            Paragraph::new(info)
-                .block(Block::default().borders(Borders::ALL).title("Info"))
-                .wrap(Wrap { trim: true }),
+            Paragraph::new(vec![
+                timeline_line(&rd.frames, frame, st.radar_playing, st.units, mono),
+                crosshair_line(rd.zoom, st.radar_center, st.radar_cursor, under),
+                Line::from(info),
+            ])
+            .block(info_block),
             chunks[1],
         );
     } else {
@@ -746,12 +2239,136 @@ fn render_radar(f: &mut Frame, area: Rect, st: &RenderState) {
                 .block(block),
             chunks[0],
         );
-        f.render_widget(
-            Paragraph::new(" ")
-                .block(Block::default().borders(Borders::ALL).title("Info")),
-            chunks[1],
-        );
+        f.render_widget(Paragraph::new(" ").block(info_block), chunks[1]);
+    }
+}
+
+// Frame `frame` as seen from `center`, with the location and the crosshair marked, and
+// the cell under the crosshair. After a pan the old frames are shifted right away, blank
+// where they have no data, until the ones for the new spot arrive.
+fn radar_view(
+    rd: &RadarData,
+    frame: usize,
+    home: &Location,
+    center: (f64, f64),
+    cursor: (i16, i16),
+) -> (Vec<Vec<RadarCell>>, RadarCell) {
+    let blank = RadarCell {
+        ch: ' ',
+        ink: RadarInk::None,
+        level: 0,
+    };
+    let (cell_w, cell_h) = (RADAR_PX / RADAR_COLS as f64, RADAR_PX / RADAR_ROWS as f64);
+    let view_px = lat_lon_to_world_px(center.0, center.1, rd.zoom, TILE_PX);
+    let data_px = lat_lon_to_world_px(rd.lat, rd.lon, rd.zoom, TILE_PX);
+    let shift_x = ((view_px.0 - data_px.0) / cell_w).round() as i64;
+    let shift_y = ((view_px.1 - data_px.1) / cell_h).round() as i64;
+
+    let cells = &rd.frames[frame].cells;
+    let mut view: Vec<Vec<RadarCell>> = (0..RADAR_ROWS as i64)
+        .map(|y| {
+            (0..RADAR_COLS as i64)
+                .map(|x| {
+                    let (sx, sy) = (x + shift_x, y + shift_y);
+                    if sx < 0 || sy < 0 {
+                        return blank;
+                    }
+                    cells
+                        .get(sy as usize)
+                        .and_then(|row| row.get(sx as usize))
+                        .copied()
+                        .unwrap_or(blank)
+                })
+                .collect()
+        })
+        .collect();
+
+    let home_px = lat_lon_to_world_px(home.lat, home.lon, rd.zoom, TILE_PX);
+    let hx = (RADAR_COLS as f64 / 2.0 + (home_px.0 - view_px.0) / cell_w).floor();
+    let hy = (RADAR_ROWS as f64 / 2.0 + (home_px.1 - view_px.1) / cell_h).floor();
+    if (0.0..RADAR_COLS as f64).contains(&hx) && (0.0..RADAR_ROWS as f64).contains(&hy) {
+        let cell = &mut view[hy as usize][hx as usize];
+        cell.ch = '⌂';
+        cell.ink = RadarInk::Marker;
     }
+
+    let cx = (RADAR_COLS as i16 / 2 + cursor.0) as usize;
+    let cy = (RADAR_ROWS as i16 / 2 + cursor.1) as usize;
+    let under = view[cy][cx];
+    view[cy][cx].ch = '+';
+    view[cy][cx].ink = RadarInk::Marker;
+    (view, under)
+}
+
+// "Crosshair: 44.5512, -69.6021 | echo: 31% (light)"
+fn crosshair_line(
+    zoom: u8,
+    center: (f64, f64),
+    cursor: (i16, i16),
+    under: RadarCell,
+) -> Line<'static> {
+    let (lat, lon) = crosshair_lat_lon(zoom, center, cursor);
+    let echo = match under.level {
+        0..=9 => "none",
+        10..=89 => "light",
+        90..=169 => "moderate",
+        _ => "heavy",
+    };
+    Line::from(format!(
+        "Crosshair: {lat:.4}, {lon:.4} | echo: {:.0}% ({echo})",
+        under.level as f64 / 2.55
+    ))
+}
+
+// Where the middle of the crosshair's cell is on the map.
+fn crosshair_lat_lon(zoom: u8, center: (f64, f64), cursor: (i16, i16)) -> (f64, f64) {
+    let (cell_w, cell_h) = (RADAR_PX / RADAR_COLS as f64, RADAR_PX / RADAR_ROWS as f64);
+    let (x, y) = lat_lon_to_world_px(center.0, center.1, zoom, TILE_PX);
+    world_px_to_lat_lon(
+        x + (cursor.0 as f64 + 0.5) * cell_w,
+        y + (cursor.1 as f64 + 0.5) * cell_h,
+        zoom,
+        TILE_PX,
+    )
+}
+
+// "▶ 14:25 (-10 min)  ─────●──┄┄┄  6/9": play state, the frame's local time, and where
+// it sits among the past (─) and nowcast (┄) frames.
+fn timeline_line(
+    frames: &[RadarFrame],
+    pos: usize,
+    playing: bool,
+    units: Units,
+    mono: bool,
+) -> Line<'static> {
+    let frame = &frames[pos];
+    let when = DateTime::from_timestamp(frame.time_utc, 0)
+        .map(|t| units.clock(t.with_timezone(&Local).naive_local()))
+        .unwrap_or_else(|| "--:--".to_string());
+    let mins = (frame.time_utc - Local::now().timestamp()) / 60;
+    let rel = if mins == 0 {
+        "now".to_string()
+    } else {
+        format!("{mins:+} min")
+    };
+    let kind = if frame.nowcast { ", nowcast" } else { "" };
+    let tick = |f: &RadarFrame| if f.nowcast { '┄' } else { '─' };
+
+    Line::from(vec![
+        Span::raw(format!(
+            "{} {when} ({rel}{kind})  ",
+            if playing { "▶" } else { "⏸" }
+        )),
+        Span::raw(frames[..pos].iter().map(tick).collect::<String>()),
+        Span::styled(
+            "●",
+            Style::default()
+                .fg(if mono { Color::White } else { Color::Cyan })
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(frames[pos + 1..].iter().map(tick).collect::<String>()),
+        Span::raw(format!("  {}/{}", pos + 1, frames.len())),
+    ])
 }
 
 fn radar_lines(cells: &[Vec<RadarCell>], mono: bool) -> Vec<Line<'static>> {
@@ -820,9 +2437,13 @@ fn fit_radar_cells(cells: &[Vec<RadarCell>], target_w: usize, target_h: usize) -
 }
 
 fn pad_radar_cells(cells: &[Vec<RadarCell>], target_w: usize, target_h: usize) -> Vec<Vec<RadarCell>> {
-    let blank = RadarCell { ch: ' ', ink: RadarInk::None };
+    let blank = RadarCell {
+        ch: ' ',
+        ink: RadarInk::None,
+        level: 0,
+    };
     let src_h = cells.len();
-    let src_w = cells.get(0).map(|row| row.len()).unwrap_or(0);
+    let src_w = cells.first().map(|row| row.len()).unwrap_or(0);
     let mut out = Vec::with_capacity(target_h);
     let pad_x = target_w.saturating_sub(src_w) / 2;
     let pad_y = target_h.saturating_sub(src_h) / 2;
@@ -833,7 +2454,7 @@ fn pad_radar_cells(cells: &[Vec<RadarCell>], target_w: usize, target_h: usize) -
             row.resize(target_w, blank);
         } else {
             let src_row = &cells[y - pad_y];
-            row.extend(std::iter::repeat(blank).take(pad_x));
+            row.extend(std::iter::repeat_n(blank, pad_x));
             row.extend_from_slice(src_row);
             row.resize(target_w, blank);
         }
@@ -846,27 +2467,142 @@ fn radar_style(ink: RadarInk, mono: bool) -> Style {
     match ink {
         RadarInk::Radar => Style::default().fg(if mono { Color::White } else { Color::Cyan }),
         RadarInk::Border => Style::default().fg(if mono { Color::Gray } else { Color::White }),
+        RadarInk::Marker => Style::default()
+            .fg(if mono { Color::White } else { Color::Yellow })
+            .add_modifier(Modifier::BOLD),
         RadarInk::None => Style::default(),
     }
 }
 
+fn render_alerts(f: &mut Frame, area: Rect, st: &RenderState) {
+    let mono = st.mono;
+    let block = Block::default().borders(Borders::ALL).title("Alerts");
+    let empty = if st.alert_rules.is_empty() {
+        Some("No alert rules (add [[alerts]] to config.toml).")
+    } else if st.forecast.is_none() {
+        Some("No forecast loaded yet (press r).")
+    } else {
+        None
+    };
+    if let Some(msg) = empty {
+        f.render_widget(
+            Paragraph::new(msg)
+                .style(Style::default().fg(if mono { Color::White } else { Color::Yellow }))
+                .block(block),
+            area,
+        );
+        return;
+    }
+
+    let rows: Vec<Row> = st
+        .alert_rules
+        .iter()
+        .map(|rule| {
+            let condition = Cell::from(alert_condition(rule, st.units));
+            match st.alerts.iter().find(|a| a.rule == *rule) {
+                Some(a) => Row::new(vec![
+                    Cell::from("⚠"),
+                    Cell::from(rule.name.clone()),
+                    condition,
+                    Cell::from(st.units.day_clock(a.first)),
+                    Cell::from(format_metric(rule.metric, a.worst, st.units)),
+                    Cell::from(format!("{} h", a.hours)),
+                ])
+                .style(if mono {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                }),
+                None => Row::new(vec![
+                    Cell::from("✓"),
+                    Cell::from(rule.name.clone()),
+                    condition,
+                    Cell::from("-"),
+                    Cell::from("-"),
+                    Cell::from("-"),
+                ]),
+            }
+        })
+        .collect();
+
+    let header = Row::new(vec!["", "Alert", "When", "From", "Worst", "Hours"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let t = Table::new(
+        rows,
+        [
+            Constraint::Length(2),
+            Constraint::Length(14),
+            Constraint::Min(30),
+            Constraint::Length(10),
+            Constraint::Length(9),
+            Constraint::Length(6),
+        ],
+    )
+    .header(header)
+    .block(block)
+    .column_spacing(1);
+
+    f.render_widget(t, area);
+}
+
+// "temp below 0.0°C in the next 24 h"
+fn alert_condition(rule: &AlertRule, units: Units) -> String {
+    let mut parts = Vec::new();
+    if let Some(b) = rule.below {
+        parts.push(format!("below {}", format_metric(rule.metric, b, units)));
+    }
+    if let Some(a) = rule.above {
+        parts.push(format!("above {}", format_metric(rule.metric, a, units)));
+    }
+    format!(
+        "{} {} in the next {} h",
+        rule.metric.label(),
+        parts.join(" or "),
+        rule.within_hours
+    )
+}
+
+fn format_metric(metric: Metric, v: f64, units: Units) -> String {
+    match metric {
+        Metric::TempC => {
+            let (t, u) = format_temp(v, units.temp);
+            format!("{t:.1}°{u}")
+        }
+        Metric::WindKph => units.format_wind(v),
+        Metric::PrecipProbPct => format!("{v:.0}%"),
+        Metric::PrecipMm => units.format_precip(v),
+    }
+}
+
 fn render_help(f: &mut Frame, area: Rect, st: &RenderState) {
     let mono = st.mono;
     let lines = vec![
         Line::from("Sources:"),
-        Line::from("  • Forecast: Open-Meteo (10-day + hourly, no API key)"),
-        Line::from("  • Radar: RainViewer tiles (past frames)"),
+        Line::from(format!(
+            "  • Forecast: {} (10-day + hourly)",
+            st.forecast_source
+        )),
+        Line::from("  • Radar: RainViewer tiles (past + nowcast frames)"),
         Line::from(""),
         Line::from("CLI examples:"),
         Line::from("  weather --lat 44.31 --lon -69.78"),
         Line::from("  weather --zip 04901"),
         Line::from("  weather --zip 04901 --country us --radar-zoom 7"),
+        Line::from("  weather --zip 04901 --name Home --save"),
+        Line::from("  weather --place \"Portland, ME\""),
+        Line::from("  weather --zip 04901 --forecast-fixture recorded.json"),
         Line::from(""),
-        Line::from("Keys: q quit | ←/→ tabs | r refresh | +/- radar zoom | f C/F | c clear error"),
+        Line::from("Keys: q quit | ←/→ tabs | r refresh | +/- radar zoom | c clear error"),
+        Line::from("      f C/F | u metric/imperial units | x extra columns"),
+        Line::from("      l / L next / previous location | p location picker | / find a place"),
+        Line::from("      space play/pause radar | ,/. step | Home/End oldest/newest frame"),
+        Line::from("      on Radar: arrows pan | Shift+arrows crosshair | h back home | Tab tabs"),
         Line::from(""),
         Line::from("Notes:"),
-        Line::from("  • Radar nowcast availability changes over time; this uses 'past' frames."),
+        Line::from("  • Radar loops over the past frames, then any nowcast frames."),
         Line::from("  • If radar is blank, try a lower zoom (e.g., 5–7)."),
+        Line::from("  • Alerts are checked locally against the forecast; set them in config.toml."),
     ];
 
     let p = Paragraph::new(lines)
@@ -883,6 +2619,41 @@ fn format_temp(c: f64, unit: TempUnit) -> (f64, &'static str) {
     }
 }
 
+// 8-point compass name for a direction in degrees.
+fn compass(deg: f64) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[((deg.rem_euclid(360.0) + 22.5) / 45.0) as usize % 8]
+}
+
+// WHO UV index categories.
+fn uv_desc(uv: f64) -> &'static str {
+    match uv {
+        u if u < 3.0 => "low",
+        u if u < 6.0 => "moderate",
+        u if u < 8.0 => "high",
+        u if u < 11.0 => "very high",
+        _ => "extreme",
+    }
+}
+
+// A change of under 1 hPa in 3 hours counts as steady.
+fn pressure_desc(change: f64) -> &'static str {
+    if change >= 1.0 {
+        "↑ rising"
+    } else if change <= -1.0 {
+        "↓ falling"
+    } else {
+        "→ steady"
+    }
+}
+
+// "2025-03-14T06:57" -> "06:57" (or "6:57 AM")
+fn clock_time(local: &str, units: Units) -> String {
+    parse_local_time(local)
+        .map(|t| units.clock(t))
+        .unwrap_or_else(|| "-".to_string())
+}
+
 fn code_icon(code: i32) -> &'static str {
     match code {
         0 => "☀",
@@ -893,7 +2664,7 @@ fn code_icon(code: i32) -> &'static str {
         61 | 63 | 65 => "🌧",
         66 | 67 => "🌧",
         71 | 73 | 75 | 77 => "❄",
-        80 | 81 | 82 => "🌧",
+        80..=82 => "🌧",
         85 | 86 => "❄",
         95 | 96 | 99 => "⛈",
         _ => "·",
@@ -938,24 +2709,65 @@ fn code_desc(code: i32) -> &'static str {
    Location (lat/lon or ZIP)
 ---------------------------- */
 
-async fn resolve_location(cli: &Cli) -> Result<Location> {
-    if let Some(zip) = &cli.zip {
-        let (lat, lon, name) = lookup_zip(&cli.country, zip).await?;
-        return Ok(Location {
-            name,
+// The location given on the command line, if any, and every match for `--place` when
+// there is more than one (the location is the best of them).
+async fn resolve_location(
+    cli: &Cli,
+    cache: Option<&Cache>,
+    geocoder: &Geocoder,
+) -> Result<(Option<Location>, Vec<Candidate>)> {
+    let mut others = Vec::new();
+    let mut loc = if let Some(zip) = &cli.zip {
+        // postal codes don't move, so a cached lookup is as good as a fresh one
+        match cache.and_then(|c| c.load_zip(&cli.country, zip)) {
+            Some(loc) => loc,
+            None => {
+                let (lat, lon, name) = lookup_zip(&cli.country, zip).await?;
+                let loc = Location { name, lat, lon };
+                if let Some(c) = cache {
+                    c.store_zip(&cli.country, zip, &loc).ok();
+                }
+                loc
+            }
+        }
+    } else if let Some(query) = &cli.place {
+        let found = match cache.and_then(|c| c.load_places(query)) {
+            Some(found) => found,
+            None => {
+                let found = geocoder.search(query).await?;
+                if let (false, Some(c)) = (found.is_empty(), cache) {
+                    c.store_places(query, &found).ok();
+                }
+                found
+            }
+        };
+        let best = found
+            .first()
+            .ok_or_else(|| anyhow!("no place matches '{query}'"))?
+            .location();
+        if found.len() > 1 {
+            others = found;
+        }
+        best
+    } else {
+        let (lat, lon) = match (cli.lat, cli.lon) {
+            (None, None) => return Ok((None, others)),
+            (lat, lon) => (
+                lat.ok_or_else(|| anyhow!("missing --lat (or use --zip or --place)"))?,
+                lon.ok_or_else(|| anyhow!("missing --lon (or use --zip or --place)"))?,
+            ),
+        };
+        Location {
+            name: "custom coords".to_string(),
             lat,
             lon,
-        });
-    }
-
-    let lat = cli.lat.ok_or_else(|| anyhow!("missing --lat (or use --zip)"))?;
-    let lon = cli.lon.ok_or_else(|| anyhow!("missing --lon (or use --zip)"))?;
+        }
+    };
 
-    Ok(Location {
-        name: "custom coords".to_string(),
-        lat,
-        lon,
-    })
+    if let Some(name) = &cli.name {
+        loc.name = name.clone();
+    }
+    Ok((Some(loc), others))
 }
 
 #[derive(Debug, Deserialize)]
@@ -988,7 +2800,7 @@ async fn lookup_zip(country: &str, postal: &str) -> Result<(f64, f64, String)> {
     let zr: ZipResp = resp.json().await.context("ZIP lookup JSON parse failed")?;
     let p = zr
         .places
-        .get(0)
+        .first()
         .ok_or_else(|| anyhow!("ZIP lookup returned no places"))?;
 
     let lat: f64 = p.latitude.parse().context("ZIP latitude parse failed")?;
@@ -998,133 +2810,6 @@ async fn lookup_zip(country: &str, postal: &str) -> Result<(f64, f64, String)> {
     Ok((lat, lon, name))
 }
 
-/* ----------------------------
-   Forecast (Open-Meteo)
----------------------------- */
-
-#[derive(Debug, Deserialize)]
-struct OpenMeteoResp {
-    timezone: String,
-
-    current: OpenMeteoCurrent,
-
-    hourly: OpenMeteoHourly,
-    daily: OpenMeteoDaily,
-}
-
-#[derive(Debug, Deserialize)]
-struct OpenMeteoCurrent {
-    time: String,
-    temperature_2m: f64,
-    wind_speed_10m: f64,
-    cloud_cover: f64,
-    precipitation: f64,
-    precipitation_probability: f64,
-    weather_code: i32,
-}
-
-#[derive(Debug, Deserialize)]
-struct OpenMeteoHourly {
-    time: Vec<String>,
-    temperature_2m: Vec<f64>,
-    precipitation: Vec<f64>,
-    precipitation_probability: Vec<f64>,
-    wind_speed_10m: Vec<f64>,
-    cloud_cover: Vec<f64>,
-    weather_code: Vec<i32>,
-}
-
-#[derive(Debug, Deserialize)]
-struct OpenMeteoDaily {
-    time: Vec<String>,
-    temperature_2m_max: Vec<f64>,
-    temperature_2m_min: Vec<f64>,
-    precipitation_probability_max: Vec<f64>,
-    weather_code: Vec<i32>,
-}
-
-async fn fetch_forecast(lat: f64, lon: f64) -> Result<ForecastData> {
-    let url = format!(
-        "https://api.open-meteo.com/v1/forecast?latitude={lat}&longitude={lon}\
-&current=temperature_2m,wind_speed_10m,cloud_cover,precipitation,precipitation_probability,weather_code\
-&hourly=temperature_2m,precipitation,precipitation_probability,wind_speed_10m,cloud_cover,weather_code\
-&daily=temperature_2m_max,temperature_2m_min,precipitation_probability_max,weather_code\
-&forecast_days=10&timezone=auto"
-    );
-
-    let c = reqwest::Client::new();
-    let resp = c
-        .get(url)
-        .send()
-        .await
-        .context("forecast request failed")?;
-
-    if !resp.status().is_success() {
-        return Err(anyhow!("forecast HTTP {}", resp.status()));
-    }
-
-    let om: OpenMeteoResp = resp.json().await.context("forecast JSON parse failed")?;
-
-    let fetched_at = Local::now();
-
-    let current = CurrentSummary {
-        temp_c: om.current.temperature_2m,
-        wind_kph: om.current.wind_speed_10m,
-        cloud_pct: om.current.cloud_cover,
-        precip_mm: om.current.precipitation,
-        precip_prob_pct: om.current.precipitation_probability,
-        code: om.current.weather_code,
-        time_local: om.current.time.clone(),
-    };
-
-    let mut hourly = Vec::new();
-    let n = om.hourly.time.len()
-        .min(om.hourly.temperature_2m.len())
-        .min(om.hourly.precipitation.len())
-        .min(om.hourly.precipitation_probability.len())
-        .min(om.hourly.wind_speed_10m.len())
-        .min(om.hourly.cloud_cover.len())
-        .min(om.hourly.weather_code.len());
-
-    for i in 0..n {
-        hourly.push(HourRow {
-            time_local: om.hourly.time[i].clone(),
-            temp_c: om.hourly.temperature_2m[i],
-            precip_mm: om.hourly.precipitation[i],
-            precip_prob_pct: om.hourly.precipitation_probability[i],
-            wind_kph: om.hourly.wind_speed_10m[i],
-            cloud_pct: om.hourly.cloud_cover[i],
-            code: om.hourly.weather_code[i],
-        });
-    }
-
-    let mut daily = Vec::new();
-    let dn = om.daily.time.len()
-        .min(om.daily.temperature_2m_max.len())
-        .min(om.daily.temperature_2m_min.len())
-        .min(om.daily.precipitation_probability_max.len())
-        .min(om.daily.weather_code.len());
-
-    for i in 0..dn {
-        let date = NaiveDate::parse_from_str(&om.daily.time[i], "%Y-%m-%d")
-            .unwrap_or_else(|_| NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
-        daily.push(DayRow {
-            date,
-            tmax_c: om.daily.temperature_2m_max[i],
-            tmin_c: om.daily.temperature_2m_min[i],
-            precip_prob_max_pct: om.daily.precipitation_probability_max[i],
-            code: om.daily.weather_code[i],
-        });
-    }
-
-    Ok(ForecastData {
-        fetched_at,
-        current,
-        hourly,
-        daily,
-    })
-}
-
 /* ----------------------------
    Radar (RainViewer)
 ---------------------------- */
@@ -1147,17 +2832,22 @@ struct RainViewerFrame {
     path: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 enum RadarInk {
     None,
     Border,
     Radar,
+    // the location and the crosshair, drawn over the map at render time
+    Marker,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 struct RadarCell {
     ch: char,
     ink: RadarInk,
+    // strongest radar echo in the cell, 0-255
+    #[serde(default)]
+    level: u8,
 }
 
 struct BasemapTiles {
@@ -1171,7 +2861,15 @@ struct BasemapTiles {
     sample_h: usize,
 }
 
-async fn fetch_radar(lat: f64, lon: f64, zoom: u8) -> Result<RadarData> {
+// Frames in `held` (radar already fetched for the same center and zoom) are reused; only
+// the ones not seen before are downloaded, all at once.
+async fn fetch_radar(
+    lat: f64,
+    lon: f64,
+    zoom: u8,
+    cache: Option<&Cache>,
+    held: Option<RadarData>,
+) -> Result<RadarData> {
     let zoom = zoom.clamp(1, 10);
 
     let c = reqwest::Client::new();
@@ -1187,26 +2885,115 @@ async fn fetch_radar(lat: f64, lon: f64, zoom: u8) -> Result<RadarData> {
         .context("radar maps json parse failed")?;
 
     let radar = maps.radar.ok_or_else(|| anyhow!("radar section missing"))?;
-    // Prefer latest past frame (most reliable over time)
-    let frame = radar
+    let listed: Vec<(&RainViewerFrame, bool)> = radar
         .past
-        .as_ref()
-        .and_then(|v| v.last())
-        .or_else(|| radar.nowcast.as_ref().and_then(|v| v.first()))
-        .ok_or_else(|| anyhow!("no radar frames available"))?;
+        .iter()
+        .flatten()
+        .map(|f| (f, false))
+        .chain(radar.nowcast.iter().flatten().map(|f| (f, true)))
+        .collect();
+    if listed.is_empty() {
+        return Err(anyhow!("no radar frames available"));
+    }
 
     // Use the "lat/lon centered" URL form from RainViewer docs.
     // Format: {path}/{size}/{z}/{lat}/{lon}/{color}/{options}.png
     // Example: /v2/radar/1609402200/512/6/44.31/-69.78/2/1_0.png
-    let size = 512;
+    let size = RADAR_PX as u32;
     let color_scheme = 2; // decent default palette
     let options = "1_0"; // smoothed, no snow mask
 
-    let url = format!(
-        "{}{}/{}/{}/{:.4}/{:.4}/{}/{}.png",
-        maps.host, frame.path, size, zoom, lat, lon, color_scheme, options
+    // Convert to braille: each cell maps to 2x4 subpixels for higher resolution.
+    let target_w = RADAR_COLS;
+    let target_h = RADAR_ROWS;
+
+    let mut held: HashMap<String, RadarFrame> = held
+        .map(|rd| rd.frames)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|f| !f.path.is_empty())
+        .map(|f| (f.path.clone(), f))
+        .collect();
+
+    let mut fetches = tokio::task::JoinSet::new();
+    for (n, (frame, _)) in listed.iter().enumerate() {
+        if held.contains_key(&frame.path) {
+            continue;
+        }
+        let url = format!(
+            "{}{}/{}/{}/{:.4}/{:.4}/{}/{}.png",
+            maps.host, frame.path, size, zoom, lat, lon, color_scheme, options
+        );
+        let c = c.clone();
+        fetches.spawn(async move { (n, fetch_radar_image(&c, url).await) });
+    }
+    let mut fetched = HashMap::new();
+    let mut last_err = None;
+    while let Some(done) = fetches.join_next().await {
+        match done {
+            Ok((n, Ok(img))) => {
+                fetched.insert(n, img);
+            }
+            Ok((_, Err(e))) => last_err = Some(e),
+            Err(e) => last_err = Some(anyhow!("radar tile fetch failed: {e}")),
+        }
+    }
+
+    let basemap = if fetched.is_empty() {
+        None
+    } else {
+        fetch_basemap_tiles(
+            lat,
+            lon,
+            zoom,
+            size as usize,
+            target_w * 2,
+            target_h * 4,
+            cache,
+        )
+        .await
+        .ok()
+    };
+
+    // One bad frame shouldn't cost the whole loop; only fail if none came through.
+    let mut frames = Vec::with_capacity(listed.len());
+    for (n, (frame, nowcast)) in listed.iter().copied().enumerate() {
+        if let Some(kept) = held.remove(&frame.path) {
+            frames.push(kept);
+        } else if let Some(img) = fetched.get(&n) {
+            frames.push(RadarFrame {
+                time_utc: frame.time,
+                nowcast,
+                path: frame.path.clone(),
+                cells: rgba_to_braille_cells(img, target_w, target_h, basemap.as_ref()),
+            });
+        }
+    }
+    if frames.is_empty() {
+        return Err(last_err.unwrap_or_else(|| anyhow!("no radar frames available")));
+    }
+
+    let nowcast = frames.iter().filter(|f| f.nowcast).count();
+    let mut info = format!(
+        "RainViewer radar ({} past + {} nowcast) + basemap",
+        frames.len() - nowcast,
+        nowcast
     );
+    if frames.len() < listed.len() {
+        info.push_str(&format!(", {} missing", listed.len() - frames.len()));
+    }
+
+    Ok(RadarData {
+        fetched_at: Local::now(),
+        lat,
+        lon,
+        zoom,
+        frames,
+        info,
+    })
+}
 
+async fn fetch_radar_image(c: &reqwest::Client, url: String) -> Result<image::RgbaImage> {
     let bytes = c
         .get(url)
         .send()
@@ -1219,25 +3006,8 @@ async fn fetch_radar(lat: f64, lon: f64, zoom: u8) -> Result<RadarData> {
         .context("radar tile read failed")?;
 
     let img = image::load_from_memory_with_format(&bytes, ImageFormat::Png)
-        .context("radar tile decode failed")?
-        .to_rgba8();
-
-    // Convert to braille: each cell maps to 2x4 subpixels for higher resolution.
-    let target_w = 76usize;
-    let target_h = 22usize;
-
-    let basemap = fetch_basemap_tiles(lat, lon, zoom, size as usize, target_w * 2, target_h * 4)
-        .await
-        .ok();
-    let cells = rgba_to_braille_cells(&img, target_w, target_h, basemap.as_ref());
-
-    Ok(RadarData {
-        fetched_at: Local::now(),
-        frame_time_utc: frame.time,
-        zoom,
-        cells,
-        info: "RainViewer radar (past) + basemap".to_string(),
-    })
+        .context("radar tile decode failed")?;
+    Ok(img.to_rgba8())
 }
 
 fn rgba_to_braille_cells(
@@ -1256,11 +3026,12 @@ fn rgba_to_braille_cells(
         let mut line = Vec::with_capacity(w);
         for xx in 0..w {
             let mut bits = [[false; 2]; 4];
+            let mut level = 0.0f32;
             let mut any = false;
             let mut any_radar = false;
             let mut any_border = false;
-            for sy in 0..4usize {
-                for sx in 0..2usize {
+            for (sy, bit_row) in bits.iter_mut().enumerate() {
+                for (sx, bit) in bit_row.iter_mut().enumerate() {
                     let px = (xx * 2 + sx) * iw / sample_w;
                     let py = (yy * 4 + sy) * ih / sample_h;
                     let p = img.get_pixel(px as u32, py as u32).0;
@@ -1270,6 +3041,7 @@ fn rgba_to_braille_cells(
                     let g = p[1] as f32 / 255.0;
                     let b = p[2] as f32 / 255.0;
                     let radar_lum = (0.2126 * r + 0.7152 * g + 0.0722 * b) * a;
+                    level = level.max(radar_lum);
                     let edge = basemap
                         .map(|m| basemap_edge_ink(m, xx * 2 + sx, yy * 4 + sy))
                         .unwrap_or(0.0);
@@ -1278,7 +3050,7 @@ fn rgba_to_braille_cells(
                     let radar_on = radar_lum > th;
                     let border_on = edge > 0.22;
                     let on = radar_on || border_on;
-                    bits[sy][sx] = on;
+                    *bit = on;
                     any |= on;
                     any_radar |= radar_on;
                     any_border |= border_on;
@@ -1292,7 +3064,11 @@ fn rgba_to_braille_cells(
             } else {
                 RadarInk::None
             };
-            line.push(RadarCell { ch, ink });
+            line.push(RadarCell {
+                ch,
+                ink,
+                level: (level * 255.0).round() as u8,
+            });
         }
         out.push(line);
     }
@@ -1325,9 +3101,10 @@ async fn fetch_basemap_tiles(
     radar_size: usize,
     sample_w: usize,
     sample_h: usize,
+    cache: Option<&Cache>,
 ) -> Result<BasemapTiles> {
     let tile_size = 256.0;
-    let n = 2_i32.pow(zoom as u32) as i32;
+    let n = 2_i32.pow(zoom as u32);
     let (center_x, center_y) = lat_lon_to_world_px(lat, lon, zoom, tile_size);
     let radar_size = radar_size as f64;
     let top_left_world_x = center_x - radar_size * 0.5;
@@ -1348,20 +3125,30 @@ async fn fetch_basemap_tiles(
         }
         for tx in min_tx..=max_tx {
             let wx = wrap_tile_x(tx, n);
-            let url = format!(
-                "https://a.basemaps.cartocdn.com/rastertiles/voyager_nolabels/{}/{}/{}.png",
-                zoom, wx, ty
-            );
-            let bytes = c
-                .get(url)
-                .send()
-                .await
-                .context("basemap tile request failed")?
-                .error_for_status()
-                .context("basemap tile HTTP error")?
-                .bytes()
-                .await
-                .context("basemap tile read failed")?;
+            let bytes = match cache.and_then(|c| c.load_tile(zoom, wx, ty)) {
+                Some(bytes) => bytes,
+                None => {
+                    let url = format!(
+                        "https://a.basemaps.cartocdn.com/rastertiles/voyager_nolabels/{}/{}/{}.png",
+                        zoom, wx, ty
+                    );
+                    let bytes = c
+                        .get(url)
+                        .send()
+                        .await
+                        .context("basemap tile request failed")?
+                        .error_for_status()
+                        .context("basemap tile HTTP error")?
+                        .bytes()
+                        .await
+                        .context("basemap tile read failed")?
+                        .to_vec();
+                    if let Some(cache) = cache {
+                        cache.store_tile(zoom, wx, ty, &bytes).ok();
+                    }
+                    bytes
+                }
+            };
             let tile = image::load_from_memory_with_format(&bytes, ImageFormat::Png)
                 .context("basemap tile decode failed")?
                 .to_rgba8();
@@ -1386,7 +3173,7 @@ fn basemap_luma(map: &BasemapTiles, sx: usize, sy: usize) -> Option<f32> {
         return None;
     }
     let tile_size = 256.0;
-    let n = 2_i32.pow(map.zoom as u32) as i32;
+    let n = 2_i32.pow(map.zoom as u32);
     let wx = map.top_left_world_x + sx as f64 * map.scale_x;
     let wy = map.top_left_world_y + sy as f64 * map.scale_y;
     let tx = (wx / tile_size).floor() as i32;
@@ -1425,6 +3212,503 @@ fn lat_lon_to_world_px(lat: f64, lon: f64, zoom: u8, tile_size: f64) -> (f64, f6
     (x * n * tile_size, y * n * tile_size)
 }
 
+// Inverse of `lat_lon_to_world_px`; x wraps around the antimeridian.
+fn world_px_to_lat_lon(x: f64, y: f64, zoom: u8, tile_size: f64) -> (f64, f64) {
+    let size = 2.0_f64.powi(zoom as i32) * tile_size;
+    let x = x.rem_euclid(size);
+    let y = y.clamp(0.0, size);
+    let lon = x / size * 360.0 - 180.0;
+    let lat = (std::f64::consts::PI * (1.0 - 2.0 * y / size))
+        .sinh()
+        .atan()
+        .to_degrees();
+    (lat, lon)
+}
+
 fn wrap_tile_x(tx: i32, n: i32) -> i32 {
     ((tx % n) + n) % n
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use ratatui::backend::TestBackend;
+    use units::{TimeFormat, UnitSystem, WindUnit};
+
+    async fn fixture_state() -> RenderState {
+        let provider = Fixture::new(PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/open_meteo_forecast.json"
+        )));
+        let mut fc = provider.fetch(44.55, -69.63).await.unwrap();
+        fc.fetched_at = Local.with_ymd_and_hms(2025, 3, 14, 14, 20, 0).unwrap();
+        let fc_fetched_at = fc.fetched_at;
+        let alert_rules = alerts::default_rules();
+        let alerts = alerts::evaluate(&alert_rules, &fc, fc.fetched_at);
+        let location = Location {
+            name: "Waterville, Maine".to_string(),
+            lat: 44.55,
+            lon: -69.63,
+        };
+        RenderState {
+            places: vec![
+                location.clone(),
+                Location {
+                    name: "Portland, Maine".to_string(),
+                    lat: 43.6615,
+                    lon: -70.2553,
+                },
+            ],
+            location,
+            current: 0,
+            picker: None,
+            search: None,
+            mono: true,
+            tab: Tab::Now,
+            units: Units::default(),
+            extra_columns: false,
+            forecast: Some(fc),
+            radar: None,
+            radar_pos: 0,
+            radar_playing: true,
+            radar_center: (44.55, -69.63),
+            radar_cursor: (0, 0),
+            forecast_every: Duration::from_secs(15 * 60),
+            radar_every: Duration::from_secs(5 * 60),
+            forecast_loading: false,
+            radar_loading: false,
+            forecast_error: None,
+            radar_error: None,
+            forecast_source: provider.describe(),
+            alert_rules,
+            alerts,
+            now: fc_fetched_at,
+        }
+    }
+
+    fn render(
+        w: u16,
+        h: u16,
+        st: &RenderState,
+        view: fn(&mut Frame, Rect, &RenderState),
+    ) -> TestBackend {
+        let mut term = Terminal::new(TestBackend::new(w, h)).unwrap();
+        term.draw(|f| view(f, f.size(), st)).unwrap();
+        term.backend().clone()
+    }
+
+    #[tokio::test]
+    async fn now_view() {
+        let st = fixture_state().await;
+        insta::assert_snapshot!(render(90, 8, &st, render_now));
+    }
+
+    #[tokio::test]
+    async fn now_view_fahrenheit() {
+        let mut st = fixture_state().await;
+        st.units.temp = TempUnit::F;
+        insta::assert_snapshot!(render(90, 8, &st, render_now));
+    }
+
+    #[tokio::test]
+    async fn now_view_imperial() {
+        let mut st = fixture_state().await;
+        st.units = Units::of(UnitSystem::Imperial);
+        insta::assert_snapshot!(render(90, 8, &st, render_now));
+    }
+
+    // A provider whose every fetch fails, as when offline.
+    #[derive(Debug)]
+    struct Offline;
+
+    #[async_trait::async_trait]
+    impl ForecastProvider for Offline {
+        async fn fetch(&self, _lat: f64, _lon: f64) -> Result<ForecastData> {
+            Err(anyhow!("network is unreachable"))
+        }
+
+        fn describe(&self) -> String {
+            "offline".to_string()
+        }
+    }
+
+    // `--once` against `Offline`, returning the exit code and what was printed.
+    async fn run_offline(
+        cache: Option<&Cache>,
+        loc: &Location,
+        format: OutputFormat,
+    ) -> (i32, String) {
+        let mut out = Vec::new();
+        let code = once::run(
+            &mut out,
+            &Offline,
+            cache,
+            loc,
+            &alerts::default_rules(),
+            Units::default(),
+            format,
+            6,
+        )
+        .await;
+        (code, String::from_utf8(out).unwrap())
+    }
+
+    #[tokio::test]
+    async fn once_exit_codes_when_the_fetch_fails() {
+        let st = fixture_state().await;
+        let loc = &st.location;
+
+        // nothing cached: only the bar formats print anything
+        let (code, out) = run_offline(None, loc, OutputFormat::Text).await;
+        assert_eq!(code, once::EXIT_FETCH_FAILED);
+        assert_eq!(out, "");
+        let (code, out) = run_offline(None, loc, OutputFormat::Waybar).await;
+        assert_eq!(code, once::EXIT_FETCH_FAILED);
+        let bar: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(bar["tooltip"], "network is unreachable");
+        assert_eq!(bar["class"], serde_json::json!(["error"]));
+
+        // a cached forecast is printed instead, marked as such
+        let dir = std::env::temp_dir().join(format!("weather-once-{}", std::process::id()));
+        let cache = Cache::at(dir.clone()).unwrap();
+        cache
+            .store_forecast(loc, st.forecast.as_ref().unwrap())
+            .unwrap();
+        let (code, out) = run_offline(Some(&cache), loc, OutputFormat::Text).await;
+        let (json_code, json) = run_offline(Some(&cache), loc, OutputFormat::Json).await;
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(code, once::EXIT_STALE);
+        assert!(
+            out.starts_with("Waterville, Maine  2025-03-14 14:15  (cached)\n"),
+            "{out}"
+        );
+        assert_eq!(json_code, once::EXIT_STALE);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["stale"], true);
+    }
+
+    #[tokio::test]
+    async fn now_view_before_the_pressure_history() {
+        let mut st = fixture_state().await;
+        if let Some(fc) = st.forecast.as_mut() {
+            // the hour three hours back is before the first hourly row
+            fc.current.time_local = "2025-03-14T01:15".to_string();
+        }
+        insta::assert_snapshot!(render(90, 8, &st, render_now));
+    }
+
+    #[test]
+    fn saving_a_location_leaves_default_alerts_out() {
+        let mut config = Config::default();
+        config.add_location(Location {
+            name: "Home".to_string(),
+            lat: 44.55,
+            lon: -69.63,
+        });
+        let text = toml::to_string_pretty(&config).unwrap();
+        assert!(!text.contains("alerts"), "{text}");
+        let reloaded: Config = toml::from_str(&text).unwrap();
+        assert_eq!(reloaded.alerts, alerts::default_rules());
+
+        // rules of your own are kept, and so is turning them off
+        config.alerts.truncate(1);
+        let text = toml::to_string_pretty(&config).unwrap();
+        assert!(text.contains("[[alerts]]"), "{text}");
+        config.alerts.clear();
+        let reloaded: Config = toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert!(reloaded.alerts.is_empty());
+    }
+
+    #[test]
+    fn unit_settings_layer_over_a_preset() {
+        let config: Config = toml::from_str(
+            r#"
+            [units]
+            system = "imperial"
+            wind = "knots"
+            time = "24h"
+            "#,
+        )
+        .unwrap();
+        let cli = UnitSettings {
+            temp: Some(TempUnit::C),
+            ..UnitSettings::default()
+        };
+        let units = cli.apply(config.units.apply(Units::default()));
+        assert_eq!(units.temp, TempUnit::C);
+        assert_eq!(units.wind, WindUnit::Knots);
+        assert_eq!(units.precip, PrecipUnit::In);
+        assert_eq!(units.pressure, PressureUnit::Inhg);
+        assert_eq!(units.time, TimeFormat::H24);
+        // a preset on the command line replaces everything from the config
+        let cli = UnitSettings {
+            system: Some(UnitSystem::Metric),
+            ..UnitSettings::default()
+        };
+        assert_eq!(cli.apply(units), Units::default());
+        assert_eq!(units.format_wind(18.52), "10 kn");
+    }
+
+    #[tokio::test]
+    async fn hourly_view() {
+        let st = fixture_state().await;
+        insta::assert_snapshot!(render(72, 28, &st, render_hourly));
+    }
+
+    #[tokio::test]
+    async fn hourly_charts_fahrenheit_narrow() {
+        let mut st = fixture_state().await;
+        st.units.temp = TempUnit::F;
+        insta::assert_snapshot!(render(40, 14, &st, |f, area, st| {
+            render_hourly_charts(f, area, st, st.forecast.as_ref().unwrap())
+        }));
+    }
+
+    #[tokio::test]
+    async fn tables_with_extra_columns() {
+        let mut st = fixture_state().await;
+        st.extra_columns = true;
+        insta::assert_snapshot!("hourly_extra_columns", render(110, 6, &st, render_hourly));
+        insta::assert_snapshot!("daily_extra_columns", render(100, 13, &st, render_daily));
+    }
+
+    #[tokio::test]
+    async fn once_text_and_waybar() {
+        let st = fixture_state().await;
+        let fc = st.forecast.as_ref().unwrap();
+        let rules = &st.alert_rules;
+        let report = once::Report::new(&st.location, fc, st.now, rules, st.units, 3, false);
+        insta::assert_snapshot!("once_text", report.format(OutputFormat::Text));
+        insta::assert_snapshot!("once_waybar", report.format(OutputFormat::Waybar));
+    }
+
+    #[test]
+    fn compass_points() {
+        assert_eq!(compass(0.0), "N");
+        assert_eq!(compass(350.0), "N");
+        assert_eq!(compass(220.0), "SW");
+        assert_eq!(compass(-90.0), "W");
+    }
+
+    #[tokio::test]
+    async fn daily_view() {
+        let st = fixture_state().await;
+        insta::assert_snapshot!(render(72, 30, &st, render_daily));
+    }
+
+    #[tokio::test]
+    async fn location_picker() {
+        let mut st = fixture_state().await;
+        st.picker = Some(1);
+        insta::assert_snapshot!(render(60, 8, &st, |f, area, st| {
+            render_picker(f, area, st, 1)
+        }));
+    }
+
+    // A stand-in for the geocoder: the recorded response for "Portland", a 404 for any
+    // other name. Returns its base URL.
+    fn geocoding_server() -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let body = std::fs::read_to_string(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/open_meteo_geocoding.json"
+        ))
+        .unwrap();
+        std::thread::spawn(move || {
+            for mut conn in listener.incoming().flatten() {
+                let mut req = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !req.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match conn.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => req.extend_from_slice(&buf[..n]),
+                    }
+                }
+                // names match case-insensitively, as they do upstream
+                let found = req
+                    .to_ascii_lowercase()
+                    .starts_with(b"get /v1/search?name=portland&");
+                let (status, body) = if found {
+                    ("200 OK", body.as_str())
+                } else {
+                    ("404 Not Found", "")
+                };
+                write!(
+                    conn,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\n\
+Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .ok();
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn place_search_narrows_by_region() {
+        let geocoder = Geocoder::new(&geocoding_server());
+        let labels =
+            |found: &[Candidate]| -> Vec<String> { found.iter().map(|c| c.label()).collect() };
+
+        let all = geocoder.search("Portland").await.unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[0].label(), "Portland, Oregon, United States");
+
+        let maine = geocoder.search("Portland, ME").await.unwrap();
+        assert_eq!(labels(&maine), ["Portland, Maine, United States"]);
+        let loc = maine[0].location();
+        assert_eq!(loc.name, "Portland, Maine");
+        assert_eq!((loc.lat, loc.lon), (43.66147, -70.25533));
+
+        for query in ["portland, australia", "Portland, AU", "Portland,Vic"] {
+            let found = geocoder.search(query).await.unwrap();
+            assert_eq!(labels(&found), ["Portland, Victoria, Australia"], "{query}");
+        }
+        assert_eq!(geocoder.search("Portland, US").await.unwrap().len(), 3);
+        assert!(geocoder.search("Portland, Ohio").await.unwrap().is_empty());
+        assert!(geocoder.search("Lisbon").await.is_err());
+        assert!(geocoder.search(" , ").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn place_search_box() {
+        let geocoder = Geocoder::new(&geocoding_server());
+        let mut st = fixture_state().await;
+        let mut search =
+            Search::with_results("Portland", geocoder.search("Portland").await.unwrap());
+        search.sel = 1;
+        st.search = Some(search);
+        insta::assert_snapshot!(render(72, 11, &st, |f, area, st| {
+            render_search(f, area, st, st.search.as_ref().unwrap())
+        }));
+    }
+
+    #[tokio::test]
+    async fn alerts_view() {
+        let st = fixture_state().await;
+        insta::assert_snapshot!(render(100, 7, &st, render_alerts));
+    }
+
+    #[tokio::test]
+    async fn alerts_look_past_the_hourly_rows() {
+        let st = fixture_state().await;
+        let mut fc = st.forecast.unwrap();
+        fc.hourly.truncate(24);
+        let rule = AlertRule {
+            name: "Warm".to_string(),
+            metric: Metric::TempC,
+            above: Some(-100.0),
+            below: None,
+            within_hours: 72,
+        };
+        let found = alerts::evaluate(&[rule], &fc, fc.fetched_at);
+        // the hourly rows end at midnight; the daily rows cover the other 62 hours
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].first.format("%m-%d %H:%M").to_string(),
+            "03-14 14:00"
+        );
+        assert_eq!(found[0].hours, 72);
+    }
+
+    #[test]
+    fn radar_timeline_marks_position_and_nowcast() {
+        let frame = |time_utc, nowcast| RadarFrame {
+            time_utc,
+            nowcast,
+            path: String::new(),
+            cells: Vec::new(),
+        };
+        let frames = [
+            frame(1_741_960_800, false),
+            frame(1_741_961_400, false),
+            frame(1_741_962_000, false),
+            frame(1_741_962_600, true),
+            frame(1_741_963_200, true),
+        ];
+        let text = timeline_line(&frames, 1, false, Units::default(), true).to_string();
+        assert!(text.starts_with("⏸ "));
+        assert!(text.ends_with("  ─●─┄┄  2/5"));
+        let text = timeline_line(&frames, 4, true, Units::default(), true).to_string();
+        assert!(text.contains(", nowcast)"));
+        assert!(text.ends_with("  ───┄●  5/5"));
+    }
+
+    #[test]
+    fn world_px_round_trips_to_lat_lon() {
+        for (lat, lon) in [
+            (44.55, -69.63),
+            (-33.87, 151.21),
+            (0.0, 0.0),
+            (64.14, -21.94),
+        ] {
+            let (x, y) = lat_lon_to_world_px(lat, lon, 7, TILE_PX);
+            let (lat2, lon2) = world_px_to_lat_lon(x, y, 7, TILE_PX);
+            assert!((lat - lat2).abs() < 1e-9 && (lon - lon2).abs() < 1e-9);
+        }
+        // a quarter of the world east of 179°E is 91°W
+        let (x, y) = lat_lon_to_world_px(10.0, 179.0, 3, TILE_PX);
+        let (_, lon) = world_px_to_lat_lon(x + 2.0 * TILE_PX, y, 3, TILE_PX);
+        assert!((lon + 91.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn radar_view_marks_home_and_follows_a_pan() {
+        let home = Location {
+            name: "home".to_string(),
+            lat: 44.55,
+            lon: -69.63,
+        };
+        let cell = |level| RadarCell {
+            ch: '⣿',
+            ink: RadarInk::Radar,
+            level,
+        };
+        // echo fades from west (255) to east (0)
+        let row: Vec<RadarCell> = (0..RADAR_COLS)
+            .map(|x| cell((255 - x * 255 / RADAR_COLS) as u8))
+            .collect();
+        let rd = RadarData {
+            fetched_at: Local::now(),
+            lat: home.lat,
+            lon: home.lon,
+            zoom: 6,
+            frames: vec![RadarFrame {
+                time_utc: 0,
+                nowcast: false,
+                path: String::new(),
+                cells: vec![row; RADAR_ROWS],
+            }],
+            info: String::new(),
+        };
+        let (mid_x, mid_y) = (RADAR_COLS / 2, RADAR_ROWS / 2);
+
+        // centered on home: the crosshair sits on top of the home marker
+        let (view, under) = radar_view(&rd, 0, &home, (home.lat, home.lon), (0, 0));
+        assert_eq!(view[mid_y][mid_x].ch, '+');
+        assert_eq!(under.ch, '⌂');
+
+        // a quarter view east: home moves west, the data shifts and runs out at the edge
+        let (x, y) = lat_lon_to_world_px(home.lat, home.lon, rd.zoom, TILE_PX);
+        let east = world_px_to_lat_lon(x + RADAR_PAN_PX, y, rd.zoom, TILE_PX);
+        let (view, under) = radar_view(&rd, 0, &home, east, (0, 0));
+        let shift = (RADAR_PAN_PX / (RADAR_PX / RADAR_COLS as f64)).round() as usize;
+        assert_eq!(view[mid_y][mid_x - shift].ch, '⌂');
+        assert_eq!(under.level, rd.frames[0].cells[mid_y][mid_x + shift].level);
+        assert_eq!(view[0][RADAR_COLS - 1].ink, RadarInk::None);
+        let (lat, lon) = crosshair_lat_lon(rd.zoom, east, (0, 0));
+        assert!((lat - home.lat).abs() < 0.3 && lon - home.lon > 2.5);
+    }
+
+    #[tokio::test]
+    async fn now_view_without_forecast() {
+        let mut st = fixture_state().await;
+        st.forecast = None;
+        insta::assert_snapshot!(render(40, 3, &st, render_now));
+    }
+}
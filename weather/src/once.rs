@@ -0,0 +1,252 @@
+use std::io::Write;
+
+use chrono::{DateTime, Local, Timelike};
+use clap::ValueEnum;
+use serde_json::json;
+
+use crate::{
+    alerts::{self, Alert, AlertRule},
+    cache::Cache,
+    code_desc, code_icon, compass, format_metric, format_temp, parse_local_time,
+    provider::ForecastProvider,
+    units::Units,
+    ForecastData, HourRow, Location,
+};
+
+// Exit codes for `--once`. Other errors (bad arguments, config, ZIP lookup) exit with 1,
+// and clap's usage errors with 2.
+pub(crate) const EXIT_OK: i32 = 0;
+// the fetch failed and nothing was cached: nothing printed (bar formats print an error)
+pub(crate) const EXIT_FETCH_FAILED: i32 = 3;
+// the fetch failed; the last cached forecast was printed instead
+pub(crate) const EXIT_STALE: i32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum OutputFormat {
+    // a few lines for a terminal
+    Text,
+    // the forecast as a JSON object
+    Json,
+    // one line of JSON for a waybar (or similar) custom module
+    Waybar,
+}
+
+// Fetch the forecast once, print it to `out` and return the exit code.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run(
+    out: &mut dyn Write,
+    provider: &dyn ForecastProvider,
+    cache: Option<&Cache>,
+    loc: &Location,
+    rules: &[AlertRule],
+    units: Units,
+    format: OutputFormat,
+    hours: usize,
+) -> i32 {
+    let (fc, code) = match provider.fetch(loc.lat, loc.lon).await {
+        Ok(fc) => {
+            if let Some(c) = cache {
+                c.store_forecast(loc, &fc).ok();
+            }
+            (fc, EXIT_OK)
+        }
+        Err(e) => {
+            eprintln!("weather: {e:#}");
+            match cache.and_then(|c| c.load_forecast(loc)) {
+                Some(fc) => (fc, EXIT_STALE),
+                None => {
+                    if format == OutputFormat::Waybar {
+                        let msg = format!("{e:#}");
+                        writeln!(
+                            out,
+                            "{}",
+                            json!({"text": "⚠", "tooltip": msg, "class": ["error"]})
+                        )
+                        .ok();
+                    }
+                    return EXIT_FETCH_FAILED;
+                }
+            }
+        }
+    };
+
+    let report = Report::new(
+        loc,
+        &fc,
+        Local::now(),
+        rules,
+        units,
+        hours,
+        code == EXIT_STALE,
+    );
+    writeln!(out, "{}", report.format(format)).ok();
+    code
+}
+
+// What gets printed, in any format.
+pub(crate) struct Report<'a> {
+    loc: &'a Location,
+    fc: &'a ForecastData,
+    hours: Vec<&'a HourRow>,
+    alerts: Vec<Alert>,
+    units: Units,
+    stale: bool,
+}
+
+impl<'a> Report<'a> {
+    pub(crate) fn new(
+        loc: &'a Location,
+        fc: &'a ForecastData,
+        now: DateTime<Local>,
+        rules: &[AlertRule],
+        units: Units,
+        hours: usize,
+        stale: bool,
+    ) -> Report<'a> {
+        // the next `hours` rows from the current hour at the location
+        let from = fc
+            .local_now(now)
+            .and_then(|t| t.date().and_hms_opt(t.hour(), 0, 0));
+        let hours = fc
+            .hourly
+            .iter()
+            .filter(|h| match (from, parse_local_time(&h.time_local)) {
+                (Some(from), Some(t)) => t >= from,
+                _ => false,
+            })
+            .take(hours)
+            .collect();
+        Report {
+            loc,
+            fc,
+            hours,
+            alerts: alerts::evaluate(rules, fc, now),
+            units,
+            stale,
+        }
+    }
+
+    pub(crate) fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Text => self.text(),
+            OutputFormat::Json => serde_json::to_string_pretty(&self.json()).unwrap_or_default(),
+            OutputFormat::Waybar => self.waybar().to_string(),
+        }
+    }
+
+    fn text(&self) -> String {
+        let cur = &self.fc.current;
+        let units = self.units;
+        let (temp, unit) = format_temp(cur.temp_c, units.temp);
+        let observed = parse_local_time(&cur.time_local)
+            .map(|t| format!("{} {}", t.format("%Y-%m-%d"), units.clock(t)))
+            .unwrap_or_else(|| cur.time_local.clone());
+        let mut lines = vec![
+            format!(
+                "{}  {observed}{}",
+                self.loc.name,
+                if self.stale { "  (cached)" } else { "" }
+            ),
+            format!(
+                "{} {}, {temp:.1}°{unit}, wind {} {}, precip {} ({:.0}%)",
+                code_icon(cur.code),
+                code_desc(cur.code),
+                units.format_wind(cur.wind_kph),
+                compass(cur.wind_dir_deg),
+                units.format_precip(cur.precip_mm),
+                cur.precip_prob_pct
+            ),
+        ];
+        for h in &self.hours {
+            let (temp, unit) = format_temp(h.temp_c, units.temp);
+            let time = parse_local_time(&h.time_local)
+                .map(|t| units.day_clock(t))
+                .unwrap_or_else(|| h.time_local.clone());
+            lines.push(format!(
+                "{time:<11} {} {temp:>5.1}°{unit} {:>4.0}% {:>7} {:>8}  {}",
+                code_icon(h.code),
+                h.precip_prob_pct,
+                units.format_precip(h.precip_mm),
+                units.format_wind(h.wind_kph),
+                code_desc(h.code)
+            ));
+        }
+        for a in &self.alerts {
+            lines.push(format!(
+                "⚠ {} from {}, up to {} ({} h)",
+                a.rule.name,
+                units.day_clock(a.first),
+                format_metric(a.rule.metric, a.worst, units),
+                a.hours
+            ));
+        }
+        lines.join("\n")
+    }
+
+    fn json(&self) -> serde_json::Value {
+        let cur = &self.fc.current;
+        json!({
+            "location": self.loc,
+            "fetched_at": self.fc.fetched_at.to_rfc3339(),
+            "timezone": self.fc.timezone,
+            "stale": self.stale,
+            "current": {
+                "time": cur.time_local,
+                "description": code_desc(cur.code),
+                "code": cur.code,
+                "temp_c": cur.temp_c,
+                "feels_like_c": cur.feels_like_c,
+                "humidity_pct": cur.humidity_pct,
+                "dew_point_c": cur.dew_point_c,
+                "wind_kph": cur.wind_kph,
+                "gust_kph": cur.gust_kph,
+                "wind_dir_deg": cur.wind_dir_deg,
+                "pressure_hpa": cur.pressure_hpa,
+                "uv_index": cur.uv_index,
+                "cloud_pct": cur.cloud_pct,
+                "precip_mm": cur.precip_mm,
+                "precip_prob_pct": cur.precip_prob_pct,
+            },
+            "hourly": self.hours.iter().map(|h| json!({
+                "time": h.time_local,
+                "description": code_desc(h.code),
+                "code": h.code,
+                "temp_c": h.temp_c,
+                "precip_mm": h.precip_mm,
+                "precip_prob_pct": h.precip_prob_pct,
+                "wind_kph": h.wind_kph,
+                "cloud_pct": h.cloud_pct,
+            })).collect::<Vec<_>>(),
+            "alerts": self.alerts.iter().map(|a| json!({
+                "name": a.rule.name,
+                "first": a.first.format("%Y-%m-%dT%H:%M").to_string(),
+                "worst": a.worst,
+                "hours": a.hours,
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    // https://github.com/Alexays/Waybar/wiki/Module:-Custom: `text` in the bar, the text
+    // report as the tooltip, and classes to style on.
+    fn waybar(&self) -> serde_json::Value {
+        let cur = &self.fc.current;
+        let (temp, unit) = format_temp(cur.temp_c, self.units.temp);
+        let mut class = Vec::new();
+        if !self.alerts.is_empty() {
+            class.push("alert");
+        }
+        if self.stale {
+            class.push("stale");
+        }
+        json!({
+            "text": format!(
+                "{} {temp:.0}°{unit}{}",
+                code_icon(cur.code),
+                if self.alerts.is_empty() { "" } else { " ⚠" }
+            ),
+            "alt": code_desc(cur.code),
+            "tooltip": self.text(),
+            "class": class,
+        })
+    }
+}
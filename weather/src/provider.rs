@@ -0,0 +1,265 @@
+use std::{fmt::Debug, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::Deserialize;
+
+use crate::{CurrentSummary, DayRow, ForecastData, HourRow};
+
+pub(crate) const OPEN_METEO_URL: &str = "https://api.open-meteo.com";
+
+// Asked for both now and per hour.
+const EXTRA_HOURLY: &str = "relative_humidity_2m,dew_point_2m,apparent_temperature,\
+surface_pressure,uv_index,wind_gusts_10m,wind_direction_10m";
+
+// Hourly rows kept from before now. The charts look back this far and the pressure trend
+// needs the reading from 3 h ago, which by default only covers hours since midnight.
+const PAST_HOURS: u32 = 12;
+
+// Where forecasts come from. Everything past this point only sees `ForecastData`.
+#[async_trait]
+pub(crate) trait ForecastProvider: Debug + Send + Sync {
+    async fn fetch(&self, lat: f64, lon: f64) -> Result<ForecastData>;
+
+    // Shown in Help, e.g. "Open-Meteo (https://api.open-meteo.com)".
+    fn describe(&self) -> String;
+}
+
+// The live Open-Meteo API, or anything serving the same JSON at `base_url`.
+#[derive(Debug)]
+pub(crate) struct OpenMeteo {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl OpenMeteo {
+    pub(crate) fn new(base_url: &str) -> OpenMeteo {
+        OpenMeteo {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for OpenMeteo {
+    async fn fetch(&self, lat: f64, lon: f64) -> Result<ForecastData> {
+        let url = format!(
+            "{}/v1/forecast?latitude={lat}&longitude={lon}\
+&current=temperature_2m,wind_speed_10m,cloud_cover,precipitation,precipitation_probability,weather_code,{EXTRA_HOURLY}\
+&hourly=temperature_2m,precipitation,precipitation_probability,wind_speed_10m,cloud_cover,weather_code,{EXTRA_HOURLY}\
+&daily=temperature_2m_max,temperature_2m_min,precipitation_probability_max,weather_code,\
+sunrise,sunset,uv_index_max,wind_gusts_10m_max,wind_direction_10m_dominant\
+&past_hours={PAST_HOURS}&forecast_days=10&timezone=auto",
+            self.base_url
+        );
+
+        let resp = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .context("forecast request failed")?;
+
+        if !resp.status().is_success() {
+            return Err(anyhow!("forecast HTTP {}", resp.status()));
+        }
+
+        let om: OpenMeteoResp = resp.json().await.context("forecast JSON parse failed")?;
+        Ok(forecast_from(om, Local::now()))
+    }
+
+    fn describe(&self) -> String {
+        format!("Open-Meteo ({})", self.base_url)
+    }
+}
+
+// A recorded Open-Meteo response on disk, served for any location. For demos, offline
+// work and tests.
+#[derive(Debug)]
+pub(crate) struct Fixture {
+    path: PathBuf,
+}
+
+impl Fixture {
+    pub(crate) fn new(path: PathBuf) -> Fixture {
+        Fixture { path }
+    }
+}
+
+#[async_trait]
+impl ForecastProvider for Fixture {
+    async fn fetch(&self, _lat: f64, _lon: f64) -> Result<ForecastData> {
+        let data = std::fs::read(&self.path)
+            .with_context(|| format!("forecast fixture read failed: {}", self.path.display()))?;
+        let om: OpenMeteoResp =
+            serde_json::from_slice(&data).context("forecast fixture JSON parse failed")?;
+        Ok(forecast_from(om, Local::now()))
+    }
+
+    fn describe(&self) -> String {
+        format!("fixture ({})", self.path.display())
+    }
+}
+
+/* ----------------------------
+   Open-Meteo JSON
+---------------------------- */
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoResp {
+    timezone: String,
+
+    current: OpenMeteoCurrent,
+
+    hourly: OpenMeteoHourly,
+    daily: OpenMeteoDaily,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoCurrent {
+    time: String,
+    temperature_2m: f64,
+    wind_speed_10m: f64,
+    cloud_cover: f64,
+    precipitation: f64,
+    precipitation_probability: f64,
+    weather_code: i32,
+    relative_humidity_2m: f64,
+    dew_point_2m: f64,
+    apparent_temperature: f64,
+    surface_pressure: f64,
+    uv_index: f64,
+    wind_gusts_10m: f64,
+    wind_direction_10m: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoHourly {
+    time: Vec<String>,
+    temperature_2m: Vec<f64>,
+    precipitation: Vec<f64>,
+    precipitation_probability: Vec<f64>,
+    wind_speed_10m: Vec<f64>,
+    cloud_cover: Vec<f64>,
+    weather_code: Vec<i32>,
+    relative_humidity_2m: Vec<f64>,
+    dew_point_2m: Vec<f64>,
+    apparent_temperature: Vec<f64>,
+    surface_pressure: Vec<f64>,
+    uv_index: Vec<f64>,
+    wind_gusts_10m: Vec<f64>,
+    wind_direction_10m: Vec<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenMeteoDaily {
+    time: Vec<String>,
+    temperature_2m_max: Vec<f64>,
+    temperature_2m_min: Vec<f64>,
+    precipitation_probability_max: Vec<f64>,
+    weather_code: Vec<i32>,
+    sunrise: Vec<String>,
+    sunset: Vec<String>,
+    uv_index_max: Vec<f64>,
+    wind_gusts_10m_max: Vec<f64>,
+    wind_direction_10m_dominant: Vec<f64>,
+}
+
+fn forecast_from(om: OpenMeteoResp, fetched_at: DateTime<Local>) -> ForecastData {
+    let current = CurrentSummary {
+        temp_c: om.current.temperature_2m,
+        wind_kph: om.current.wind_speed_10m,
+        cloud_pct: om.current.cloud_cover,
+        precip_mm: om.current.precipitation,
+        precip_prob_pct: om.current.precipitation_probability,
+        code: om.current.weather_code,
+        time_local: om.current.time.clone(),
+        humidity_pct: om.current.relative_humidity_2m,
+        dew_point_c: om.current.dew_point_2m,
+        feels_like_c: om.current.apparent_temperature,
+        pressure_hpa: om.current.surface_pressure,
+        uv_index: om.current.uv_index,
+        gust_kph: om.current.wind_gusts_10m,
+        wind_dir_deg: om.current.wind_direction_10m,
+    };
+
+    let mut hourly = Vec::new();
+    let n = om
+        .hourly
+        .time
+        .len()
+        .min(om.hourly.temperature_2m.len())
+        .min(om.hourly.precipitation.len())
+        .min(om.hourly.precipitation_probability.len())
+        .min(om.hourly.wind_speed_10m.len())
+        .min(om.hourly.cloud_cover.len())
+        .min(om.hourly.weather_code.len())
+        .min(om.hourly.relative_humidity_2m.len())
+        .min(om.hourly.dew_point_2m.len())
+        .min(om.hourly.apparent_temperature.len())
+        .min(om.hourly.surface_pressure.len())
+        .min(om.hourly.uv_index.len())
+        .min(om.hourly.wind_gusts_10m.len())
+        .min(om.hourly.wind_direction_10m.len());
+
+    for i in 0..n {
+        hourly.push(HourRow {
+            time_local: om.hourly.time[i].clone(),
+            temp_c: om.hourly.temperature_2m[i],
+            precip_mm: om.hourly.precipitation[i],
+            precip_prob_pct: om.hourly.precipitation_probability[i],
+            wind_kph: om.hourly.wind_speed_10m[i],
+            cloud_pct: om.hourly.cloud_cover[i],
+            code: om.hourly.weather_code[i],
+            humidity_pct: om.hourly.relative_humidity_2m[i],
+            dew_point_c: om.hourly.dew_point_2m[i],
+            feels_like_c: om.hourly.apparent_temperature[i],
+            pressure_hpa: om.hourly.surface_pressure[i],
+            uv_index: om.hourly.uv_index[i],
+            gust_kph: om.hourly.wind_gusts_10m[i],
+            wind_dir_deg: om.hourly.wind_direction_10m[i],
+        });
+    }
+
+    let mut daily = Vec::new();
+    let dn = om
+        .daily
+        .time
+        .len()
+        .min(om.daily.temperature_2m_max.len())
+        .min(om.daily.temperature_2m_min.len())
+        .min(om.daily.precipitation_probability_max.len())
+        .min(om.daily.weather_code.len())
+        .min(om.daily.sunrise.len())
+        .min(om.daily.sunset.len())
+        .min(om.daily.uv_index_max.len())
+        .min(om.daily.wind_gusts_10m_max.len())
+        .min(om.daily.wind_direction_10m_dominant.len());
+
+    for i in 0..dn {
+        let date = NaiveDate::parse_from_str(&om.daily.time[i], "%Y-%m-%d")
+            .unwrap_or_else(|_| NaiveDate::from_ymd_opt(1970, 1, 1).unwrap());
+        daily.push(DayRow {
+            date,
+            tmax_c: om.daily.temperature_2m_max[i],
+            tmin_c: om.daily.temperature_2m_min[i],
+            precip_prob_max_pct: om.daily.precipitation_probability_max[i],
+            code: om.daily.weather_code[i],
+            sunrise_local: om.daily.sunrise[i].clone(),
+            sunset_local: om.daily.sunset[i].clone(),
+            uv_max: om.daily.uv_index_max[i],
+            gust_max_kph: om.daily.wind_gusts_10m_max[i],
+            wind_dir_deg: om.daily.wind_direction_10m_dominant[i],
+        });
+    }
+
+    ForecastData {
+        fetched_at,
+        timezone: om.timezone,
+        current,
+        hourly,
+        daily,
+    }
+}
@@ -0,0 +1,274 @@
+use chrono::{DateTime, Local, NaiveDateTime};
+use clap::{Args, ValueEnum};
+use serde::{Deserialize, Serialize};
+
+// The forecast always arrives in metric (°C, km/h, mm, hPa); these only change how it is
+// shown.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TempUnit {
+    C,
+    F,
+}
+
+impl TempUnit {
+    pub(crate) fn toggle(self) -> Self {
+        match self {
+            TempUnit::C => TempUnit::F,
+            TempUnit::F => TempUnit::C,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum WindUnit {
+    Kph,
+    Mph,
+    Ms,
+    Knots,
+}
+
+impl WindUnit {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            WindUnit::Kph => "km/h",
+            WindUnit::Mph => "mph",
+            WindUnit::Ms => "m/s",
+            WindUnit::Knots => "kn",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PrecipUnit {
+    Mm,
+    In,
+}
+
+impl PrecipUnit {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            PrecipUnit::Mm => "mm",
+            PrecipUnit::In => "in",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum PressureUnit {
+    Hpa,
+    Inhg,
+    Mmhg,
+}
+
+impl PressureUnit {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            PressureUnit::Hpa => "hPa",
+            PressureUnit::Inhg => "inHg",
+            PressureUnit::Mmhg => "mmHg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+pub(crate) enum TimeFormat {
+    #[serde(rename = "24h")]
+    #[value(name = "24h")]
+    H24,
+    #[serde(rename = "12h")]
+    #[value(name = "12h")]
+    H12,
+}
+
+// Presets that set every unit at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Units {
+    pub(crate) temp: TempUnit,
+    pub(crate) wind: WindUnit,
+    pub(crate) precip: PrecipUnit,
+    pub(crate) pressure: PressureUnit,
+    pub(crate) time: TimeFormat,
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Units::of(UnitSystem::Metric)
+    }
+}
+
+impl Units {
+    pub(crate) fn of(system: UnitSystem) -> Units {
+        match system {
+            UnitSystem::Metric => Units {
+                temp: TempUnit::C,
+                wind: WindUnit::Kph,
+                precip: PrecipUnit::Mm,
+                pressure: PressureUnit::Hpa,
+                time: TimeFormat::H24,
+            },
+            UnitSystem::Imperial => Units {
+                temp: TempUnit::F,
+                wind: WindUnit::Mph,
+                precip: PrecipUnit::In,
+                pressure: PressureUnit::Inhg,
+                time: TimeFormat::H12,
+            },
+        }
+    }
+
+    // Imperial, or metric if that is what is set already.
+    pub(crate) fn toggle_system(self) -> Units {
+        if self == Units::of(UnitSystem::Imperial) {
+            Units::of(UnitSystem::Metric)
+        } else {
+            Units::of(UnitSystem::Imperial)
+        }
+    }
+
+    pub(crate) fn wind(self, kph: f64) -> f64 {
+        match self.wind {
+            WindUnit::Kph => kph,
+            WindUnit::Mph => kph / 1.609_344,
+            WindUnit::Ms => kph / 3.6,
+            WindUnit::Knots => kph / 1.852,
+        }
+    }
+
+    // "13 km/h", "8 mph"
+    pub(crate) fn format_wind(self, kph: f64) -> String {
+        format!("{:.0} {}", self.wind(kph), self.wind.label())
+    }
+
+    pub(crate) fn precip(self, mm: f64) -> f64 {
+        match self.precip {
+            PrecipUnit::Mm => mm,
+            PrecipUnit::In => mm / 25.4,
+        }
+    }
+
+    // "1.1 mm", "0.04 in"
+    pub(crate) fn format_precip(self, mm: f64) -> String {
+        match self.precip {
+            PrecipUnit::Mm => format!("{:.1} mm", mm),
+            PrecipUnit::In => format!("{:.2} in", self.precip(mm)),
+        }
+    }
+
+    pub(crate) fn pressure(self, hpa: f64) -> f64 {
+        match self.pressure {
+            PressureUnit::Hpa => hpa,
+            PressureUnit::Inhg => hpa * 0.029_53,
+            PressureUnit::Mmhg => hpa * 0.750_062,
+        }
+    }
+
+    // "1003.4 hPa", "29.63 inHg"
+    pub(crate) fn format_pressure(self, hpa: f64) -> String {
+        let decimals = match self.pressure {
+            PressureUnit::Inhg => 2,
+            _ => 1,
+        };
+        format!(
+            "{:.*} {}",
+            decimals,
+            self.pressure(hpa),
+            self.pressure.label()
+        )
+    }
+
+    // "14:00", "2:00 PM"
+    pub(crate) fn clock(self, t: NaiveDateTime) -> String {
+        t.format(self.clock_fmt()).to_string()
+    }
+
+    // "Fri 14:00", "Fri 2:00 PM"
+    pub(crate) fn day_clock(self, t: NaiveDateTime) -> String {
+        format!("{} {}", t.format("%a"), self.clock(t))
+    }
+
+    // "2025-03-14 14:20:00", "2025-03-14 2:20:00 PM"
+    pub(crate) fn timestamp(self, t: DateTime<Local>) -> String {
+        let fmt = match self.time {
+            TimeFormat::H24 => "%Y-%m-%d %H:%M:%S",
+            TimeFormat::H12 => "%Y-%m-%d %-I:%M:%S %p",
+        };
+        t.format(fmt).to_string()
+    }
+
+    fn clock_fmt(self) -> &'static str {
+        match self.time {
+            TimeFormat::H24 => "%H:%M",
+            TimeFormat::H12 => "%-I:%M %p",
+        }
+    }
+}
+
+// Unit choices, from the `[units]` table of the config file or the command line. `system`
+// picks a preset; the others override it one at a time.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Args)]
+pub(crate) struct UnitSettings {
+    /// Unit system: metric or imperial (the unit options below override parts of it)
+    #[arg(long = "units", value_enum)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) system: Option<UnitSystem>,
+
+    /// Temperature unit
+    #[arg(long = "temp-unit", value_enum)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) temp: Option<TempUnit>,
+
+    /// Wind speed unit
+    #[arg(long = "wind-unit", value_enum)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) wind: Option<WindUnit>,
+
+    /// Precipitation unit
+    #[arg(long = "precip-unit", value_enum)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) precip: Option<PrecipUnit>,
+
+    /// Pressure unit
+    #[arg(long = "pressure-unit", value_enum)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) pressure: Option<PressureUnit>,
+
+    /// Clock format
+    #[arg(long = "time-format", value_enum)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) time: Option<TimeFormat>,
+}
+
+impl UnitSettings {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.system.is_none()
+            && self.temp.is_none()
+            && self.wind.is_none()
+            && self.precip.is_none()
+            && self.pressure.is_none()
+            && self.time.is_none()
+    }
+
+    // `base` with these settings applied on top.
+    pub(crate) fn apply(&self, base: Units) -> Units {
+        let base = self.system.map_or(base, Units::of);
+        Units {
+            temp: self.temp.unwrap_or(base.temp),
+            wind: self.wind.unwrap_or(base.wind),
+            precip: self.precip.unwrap_or(base.precip),
+            pressure: self.pressure.unwrap_or(base.pressure),
+            time: self.time.unwrap_or(base.time),
+        }
+    }
+}